@@ -1,15 +1,15 @@
+pub use num_bigint::BigUint;
+
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+mod serializer;
 
 /// Represent a number.
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+///
+/// Once serialized, an integer is written as a number if it fits in 64 bits, and as a string of decimal digits otherwise.
 #[derive(PartialEq, Eq, Debug, Clone)]
-#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum Cardinality<U> {
     /// An infinite number.
     Infinite,
     /// A finite number.
     Integer(U),
-    /// A finite number too big to be represented.
-    BigInteger,
 }
@@ -0,0 +1,89 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::*;
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename = "Cardinality")]
+enum SerializedCardinality {
+    Infinite,
+    Integer(SerializedInteger),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum SerializedInteger {
+    Number(u64),
+    Digits(String),
+}
+
+impl<U: Display> Serialize for Cardinality<U> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Cardinality::Infinite => SerializedCardinality::Infinite,
+            Cardinality::Integer(integer) => {
+                let digits = integer.to_string();
+                SerializedCardinality::Integer(match digits.parse() {
+                    Ok(number) => SerializedInteger::Number(number),
+                    Err(_) => SerializedInteger::Digits(digits),
+                })
+            }
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, U: FromStr> Deserialize<'de> for Cardinality<U> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let digits = match SerializedCardinality::deserialize(deserializer)? {
+            SerializedCardinality::Infinite => return Ok(Cardinality::Infinite),
+            SerializedCardinality::Integer(SerializedInteger::Number(number)) => number.to_string(),
+            SerializedCardinality::Integer(SerializedInteger::Digits(digits)) => digits,
+        };
+        match digits.parse() {
+            Ok(integer) => Ok(Cardinality::Integer(integer)),
+            Err(_) => Err(de::Error::custom(format!(
+                "`{digits}` is not a valid cardinality"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() -> Result<(), String> {
+        assert_serialization(
+            Cardinality::Integer(BigUint::from(42u32)),
+            r#"{"type":"Integer","value":42}"#,
+        );
+        assert_serialization(
+            Cardinality::Integer(BigUint::from(26u32).pow(20)),
+            r#"{"type":"Integer","value":"19928148895209409152340197376"}"#,
+        );
+        assert_serialization(Cardinality::Infinite, r#"{"type":"Infinite"}"#);
+
+        assert!(
+            serde_json::from_str::<Cardinality<BigUint>>(r#"{"type":"Integer","value":"-1"}"#)
+                .is_err()
+        );
+        Ok(())
+    }
+
+    fn assert_serialization(cardinality: Cardinality<BigUint>, expected: &str) {
+        let serialized = serde_json::to_string(&cardinality).unwrap();
+        assert_eq!(expected, serialized);
+
+        let unserialized: Cardinality<BigUint> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(cardinality, unserialized);
+    }
+}
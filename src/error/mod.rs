@@ -15,6 +15,10 @@ pub enum EngineError {
     AutomatonHasTooManyStates,
     /// The regular expression can not be parsed.
     RegexSyntaxError(String),
+    /// The regular expression can not be written in the requested syntax.
+    RegexCannotBeExpressed(String),
+    /// The JSON schema can not be converted.
+    InvalidJsonSchema(String),
     /// Too many terms are used in the operation.
     TooMuchTerms(usize, usize),
     /// The provided range can not be built from the spanning set.
@@ -33,6 +37,8 @@ impl fmt::Display for EngineError {
             EngineError::AutomatonShouldBeDeterministic => write!(f, "The given automaton should be deterministic."),
             EngineError::AutomatonHasTooManyStates => write!(f, "The automaton has too many states."),
             EngineError::RegexSyntaxError(err) => write!(f, "{err}."),
+            EngineError::RegexCannotBeExpressed(err) => write!(f, "{err}."),
+            EngineError::InvalidJsonSchema(err) => write!(f, "{err}."),
             EngineError::TooMuchTerms(max, got) => write!(f, "Too many terms are used in this operation, the maximum allowed for your plan is {max} and you used {got}."),
             EngineError::TokenError(err) =>  write!(f, "{err}."),
             EngineError::ConditionInvalidRange => write!(f, "The provided range can not be built from the spanning set."),
@@ -53,6 +59,8 @@ impl EngineError {
             EngineError::AutomatonShouldBeDeterministic => true,
             EngineError::AutomatonHasTooManyStates => false,
             EngineError::RegexSyntaxError(_) => false,
+            EngineError::RegexCannotBeExpressed(_) => false,
+            EngineError::InvalidJsonSchema(_) => false,
             EngineError::TooMuchTerms(_, _) => false,
             EngineError::TokenError(_) => false,
             EngineError::ConditionInvalidRange => true,
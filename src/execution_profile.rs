@@ -108,6 +108,17 @@ impl ExecutionProfile {
             Ok(())
         }
     }
+
+    /// Assert that `number_of_states` is lower than `max_number_of_states`.
+    ///
+    /// Return [`EngineError::AutomatonHasTooManyStates`] otherwise.
+    pub fn assert_max_number_of_states(&self, number_of_states: usize) -> Result<(), EngineError> {
+        if number_of_states >= self.max_number_of_states {
+            Err(EngineError::AutomatonHasTooManyStates)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 /// Hold [`ExecutionProfile`] on the current thread.
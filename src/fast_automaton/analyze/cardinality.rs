@@ -1,11 +1,13 @@
-use std::hash::BuildHasherDefault;
+use std::{borrow::Cow, hash::BuildHasherDefault};
+
+use crate::{cardinality::BigUint, execution_profile::ThreadLocalParams};
 
 use super::*;
 
 impl FastAutomaton {
-    pub fn get_cardinality(&self) -> Option<Cardinality<u32>> {
+    pub fn get_cardinality(&self) -> Option<Cardinality<BigUint>> {
         if self.is_empty() {
-            return Some(Cardinality::Integer(0));
+            return Some(Cardinality::Integer(BigUint::ZERO));
         } else if self.cyclic || self.is_total() {
             return Some(Cardinality::Infinite);
         } else if !self.deterministic {
@@ -19,46 +21,82 @@ impl FastAutomaton {
         let topologically_sorted_states = topologically_sorted_states.unwrap();
 
         let len = self.transitions.len();
-        let mut distances: IntMap<usize, u32> =
+        let mut distances: IntMap<usize, BigUint> =
             IntMap::with_capacity_and_hasher(len, BuildHasherDefault::default());
 
-        distances.insert(self.start_state, 1);
+        distances.insert(self.start_state, BigUint::from(1u32));
         for state in topologically_sorted_states {
-            let current_distance = *distances.entry(state).or_insert(0);
+            let current_distance = distances.entry(state).or_default().clone();
             if let Some(to_states) = self.transitions.get(state) {
                 for (to_state, condition) in to_states {
-                    if let Some(distance) = current_distance.checked_mul(
-                        condition
+                    let distance = &current_distance
+                        * condition
                             .get_cardinality(&self.spanning_set)
-                            .expect("It should be possible to get the cardinality of a condition."),
-                    ) {
-                        if let Some(new_distance) =
-                            distances.get(to_state).unwrap_or(&0).checked_add(distance)
-                        {
-                            distances.insert(*to_state, new_distance);
-                            continue;
-                        }
-                    }
-
-                    return Some(Cardinality::BigInteger);
+                            .expect("It should be possible to get the cardinality of a condition.");
+                    *distances.entry(*to_state).or_default() += distance;
                 }
             }
         }
 
-        let mut temp_cardinality: u32 = 0;
+        let mut cardinality = BigUint::ZERO;
         for accept_state in &self.accept_states {
             if let Some(distance) = distances.get(accept_state) {
-                if let Some(add) = temp_cardinality.checked_add(*distance) {
-                    temp_cardinality = add;
-                    continue;
+                cardinality += distance;
+            }
+        }
+        Some(Cardinality::Integer(cardinality))
+    }
+
+    /// Count the number of matched strings of each length from `0` to `max_len`, the element at index `i` being the number of strings of length `i`.
+    ///
+    /// Unlike [`Self::get_cardinality`], it also works on cyclic automata. The automaton is determinized first if needed.
+    pub fn count_by_length(&self, max_len: usize) -> Result<Vec<BigUint>, EngineError> {
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+        // The counts are added one length at a time, so that a large `max_len` is bounded by the timeout.
+        let mut counts = Vec::new();
+
+        let automaton = if self.deterministic {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(self.determinize()?)
+        };
+
+        let mut transitions = Vec::with_capacity(automaton.get_number_of_states());
+        for from_state in automaton.transitions_iter() {
+            for (to_state, condition) in
+                automaton.transitions_from_state_enumerate_iter(&from_state)
+            {
+                let cardinality = condition.get_cardinality(&automaton.spanning_set)?;
+                transitions.push((from_state, *to_state, BigUint::from(cardinality)));
+            }
+        }
+
+        let mut paths: IntMap<State, BigUint> =
+            IntMap::with_capacity_and_hasher(automaton.transitions.len(), Default::default());
+        paths.insert(automaton.start_state, BigUint::from(1u32));
+        for _ in 0..=max_len {
+            execution_profile.assert_not_timed_out()?;
+            let mut count = BigUint::ZERO;
+            for (state, number_of_paths) in &paths {
+                if automaton.accept_states.contains(state) {
+                    count += number_of_paths;
+                }
+            }
+            counts.push(count);
+
+            let mut next_paths: IntMap<State, BigUint> =
+                IntMap::with_capacity_and_hasher(paths.len(), Default::default());
+            for (from_state, to_state, cardinality) in &transitions {
+                if let Some(number_of_paths) = paths.get(from_state) {
+                    *next_paths.entry(*to_state).or_default() += number_of_paths * cardinality;
                 }
-                return Some(Cardinality::BigInteger);
             }
+            paths = next_paths;
         }
-        Some(Cardinality::Integer(temp_cardinality))
+        Ok(counts)
     }
 
-    fn topological_sorted_states(&self) -> Option<Vec<usize>> {
+    pub(crate) fn topological_sorted_states(&self) -> Option<Vec<usize>> {
         let len = self.get_number_of_states();
         let mut in_degree: IntMap<usize, i32> =
             IntMap::with_capacity_and_hasher(len, BuildHasherDefault::default());
@@ -96,3 +134,67 @@ impl FastAutomaton {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use crate::{
+        cardinality::BigUint,
+        error::EngineError,
+        execution_profile::{ExecutionProfile, ThreadLocalParams},
+        regex::RegularExpression,
+    };
+
+    #[test]
+    fn test_count_by_length() -> Result<(), String> {
+        assert_count_by_length("[ab]*", 4, &[1, 2, 4, 8, 16]);
+        assert_count_by_length("(ab|cd)*", 4, &[1, 0, 2, 0, 4]);
+        assert_count_by_length("a*|a{2}b", 3, &[1, 1, 1, 2]);
+        assert_count_by_length("x{2,3}", 5, &[0, 0, 1, 1, 0, 0]);
+        assert_count_by_length("[]", 2, &[0, 0, 0]);
+        assert_count_by_length("", 2, &[1, 0, 0]);
+
+        let automaton = RegularExpression::new("[a-z]{10}|x*")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let counts = automaton.count_by_length(10).unwrap();
+        assert_eq!(BigUint::from(26u32).pow(10), counts[10]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_by_length_timeout() -> Result<(), String> {
+        let automaton = RegularExpression::new("[ab]*")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        ThreadLocalParams::init_profile(&ExecutionProfile {
+            max_number_of_states: 8192,
+            start_execution_time: Some(SystemTime::now()),
+            execution_timeout: 10,
+            max_number_of_terms: 50,
+        });
+        assert_eq!(
+            EngineError::OperationTimeOutError,
+            automaton.count_by_length(usize::MAX).unwrap_err()
+        );
+        Ok(())
+    }
+
+    fn assert_count_by_length(regex: &str, max_len: usize, expected: &[u32]) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let expected: Vec<BigUint> = expected.iter().map(|count| BigUint::from(*count)).collect();
+        assert_eq!(
+            expected,
+            automaton.count_by_length(max_len).unwrap(),
+            "{regex}"
+        );
+    }
+}
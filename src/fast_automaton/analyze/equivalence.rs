@@ -22,6 +22,26 @@ impl FastAutomaton {
 
         Ok(!self_complement.has_intersection(other)?)
     }
+
+    /// Return the shortest string matched by only one of the two automata, or `None` if they are equivalent.
+    pub fn get_distinguishing_string(
+        &self,
+        other: &FastAutomaton,
+    ) -> Result<Option<String>, EngineError> {
+        let witness_1 = self.get_subset_counterexample(other)?;
+        let witness_2 = other.get_subset_counterexample(self)?;
+
+        Ok(match (witness_1, witness_2) {
+            (Some(witness_1), Some(witness_2)) => {
+                if witness_2.chars().count() < witness_1.chars().count() {
+                    Some(witness_2)
+                } else {
+                    Some(witness_1)
+                }
+            }
+            (witness_1, witness_2) => witness_1.or(witness_2),
+        })
+    }
 }
 
 #[cfg(test)]
@@ -71,14 +91,23 @@ mod tests {
     fn assert_equivalent(regex_1: &RegularExpression, regex_2: &RegularExpression, expected: bool) {
         println!("{regex_1} and {regex_2}");
         let automaton_1 = regex_1.to_automaton().unwrap();
-        assert_eq!(true, automaton_1.is_equivalent_of(&automaton_1).unwrap());
+        assert!(automaton_1.is_equivalent_of(&automaton_1).unwrap());
 
         let automaton_2 = regex_2.to_automaton().unwrap();
-        assert_eq!(true, automaton_2.is_equivalent_of(&automaton_2).unwrap());
+        assert!(automaton_2.is_equivalent_of(&automaton_2).unwrap());
 
         assert_eq!(
             expected,
             automaton_1.is_equivalent_of(&automaton_2).unwrap()
         );
+
+        let distinguishing_string = automaton_1.get_distinguishing_string(&automaton_2).unwrap();
+        assert_eq!(expected, distinguishing_string.is_none());
+        if let Some(distinguishing_string) = distinguishing_string {
+            assert_ne!(
+                automaton_1.match_string(&distinguishing_string),
+                automaton_2.match_string(&distinguishing_string)
+            );
+        }
     }
 }
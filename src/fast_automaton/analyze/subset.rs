@@ -15,12 +15,27 @@ impl FastAutomaton {
 
         Ok(!self.has_intersection(&other)?)
     }
+
+    /// Return the shortest string matched by `self` but not by `other`, or `None` if `self` is a subset of `other`.
+    pub fn get_subset_counterexample(
+        &self,
+        other: &FastAutomaton,
+    ) -> Result<Option<String>, EngineError> {
+        if self.is_empty() || other.is_total() || self == other {
+            return Ok(None);
+        }
+
+        let mut other = other.determinize()?;
+        other.complement()?;
+
+        self.get_intersection_witness(&other)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::regex::RegularExpression;
+    use crate::{fast_automaton::FastAutomaton, regex::RegularExpression};
 
     #[test]
     fn test_subset() -> Result<(), String> {
@@ -79,10 +94,10 @@ mod tests {
     ) {
         println!("{regex_1} and {regex_2}");
         let automaton_1 = regex_1.to_automaton().unwrap();
-        assert_eq!(true, automaton_1.is_subset_of(&automaton_1).unwrap());
+        assert!(automaton_1.is_subset_of(&automaton_1).unwrap());
 
         let automaton_2 = regex_2.to_automaton().unwrap();
-        assert_eq!(true, automaton_2.is_subset_of(&automaton_2).unwrap());
+        assert!(automaton_2.is_subset_of(&automaton_2).unwrap());
 
         assert_eq!(
             expected_1_2,
@@ -92,5 +107,42 @@ mod tests {
             expected_2_1,
             automaton_2.is_subset_of(&automaton_1).unwrap()
         );
+
+        assert_subset_counterexample(&automaton_1, &automaton_2, expected_1_2);
+        assert_subset_counterexample(&automaton_2, &automaton_1, expected_2_1);
+    }
+
+    fn assert_subset_counterexample(
+        automaton_1: &FastAutomaton,
+        automaton_2: &FastAutomaton,
+        expected: bool,
+    ) {
+        let counterexample = automaton_1.get_subset_counterexample(automaton_2).unwrap();
+        assert_eq!(expected, counterexample.is_none());
+        if let Some(counterexample) = counterexample {
+            assert!(automaton_1.match_string(&counterexample));
+            assert!(!automaton_2.match_string(&counterexample));
+        }
+    }
+
+    #[test]
+    fn test_subset_counterexample() -> Result<(), String> {
+        let automaton_1 = RegularExpression::new("test.{0,50}other")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let automaton_2 = RegularExpression::new("test.{0,49}other")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let counterexample = automaton_1
+            .get_subset_counterexample(&automaton_2)
+            .unwrap()
+            .unwrap();
+        assert_eq!(59, counterexample.chars().count());
+        assert!(automaton_1.match_string(&counterexample));
+        assert!(!automaton_2.match_string(&counterexample));
+        Ok(())
     }
 }
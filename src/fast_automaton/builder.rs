@@ -52,7 +52,7 @@ impl FastAutomaton {
         }
         let new_state = automaton.new_state();
 
-        let spanning_set = SpanningSet::compute_spanning_set(&[range.clone()]);
+        let spanning_set = SpanningSet::compute_spanning_set(std::slice::from_ref(range));
         let condition = Condition::from_range(range, &spanning_set)?;
         automaton.spanning_set = spanning_set;
         automaton.add_transition_to(0, new_state, &condition);
@@ -85,6 +85,7 @@ impl FastAutomaton {
     #[inline]
     pub fn apply_model(&mut self, model: &FastAutomaton) {
         self.transitions = model.transitions.clone();
+        self.transitions_in = model.transitions_in.clone();
         self.start_state = model.start_state;
         self.accept_states = model.accept_states.clone();
         self.removed_states = model.removed_states.clone();
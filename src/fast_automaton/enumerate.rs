@@ -0,0 +1,431 @@
+use std::borrow::Cow;
+
+use crate::EngineError;
+
+use super::*;
+
+/// A lazy iterator over the strings matched by a [`FastAutomaton`], in shortlex order: shorter strings first, strings of the same length in ascending order of their characters.
+///
+/// The iterator only holds the current string and its path in the automaton, so it can enumerate an unbounded number of strings in constant memory per length.
+/// To resume an interrupted enumeration, create a new iterator with [`FastAutomaton::strings_after`] from the last string received.
+#[derive(Clone, Debug)]
+pub struct StringIterator {
+    start_state: State,
+    /// The transitions of each state as `(from, to, to_state)`, sorted by character.
+    transitions: Vec<Vec<(char, char, State)>>,
+    reachable_states: IntSet<State>,
+    /// `completable[len]` is the set of states from which an accept state can be reached with a string of length `len`.
+    completable: Vec<IntSet<State>>,
+    len: usize,
+    /// The states along the current string, with the lowest character still to try from each of them.
+    stack: Vec<(State, Option<char>)>,
+    prefix: Vec<char>,
+    exhausted: bool,
+}
+
+impl FastAutomaton {
+    /// Return a lazy iterator over the matched strings in shortlex order, the automaton is determinized first if needed.
+    pub fn strings(&self) -> Result<StringIterator, EngineError> {
+        StringIterator::new(self)
+    }
+
+    /// Return a lazy iterator over the matched strings coming strictly after `last` in shortlex order, `last` does not have to be matched.
+    pub fn strings_after(&self, last: &str) -> Result<StringIterator, EngineError> {
+        let mut iterator = StringIterator::new(self)?;
+        iterator.seek_after(last);
+        Ok(iterator)
+    }
+
+    /// Return the shortest matched string, the lowest one in ascending order of characters if there are several, or `None` if the automaton is empty.
+    pub fn shortest_string(&self) -> Result<Option<String>, EngineError> {
+        Ok(self.strings()?.next())
+    }
+
+    /// Return the longest matched string, the lowest one in ascending order of characters if there are several, or `None` if the automaton is empty or matches strings of unbounded length.
+    pub fn longest_string(&self) -> Result<Option<String>, EngineError> {
+        let mut iterator = StringIterator::new(self)?;
+        if iterator.exhausted {
+            return Ok(None);
+        }
+        // A string longer than the number of states goes through a cycle, which can then be repeated.
+        let number_of_states = iterator.reachable_states.len();
+        iterator.compute_completable(number_of_states);
+        if !iterator.completable[number_of_states].is_empty() {
+            return Ok(None);
+        }
+        let len = (0..number_of_states)
+            .rev()
+            .find(|len| iterator.completable[*len].contains(&iterator.start_state));
+        Ok(len.and_then(|len| iterator.get_extremum_of_length(len, false)))
+    }
+
+    /// Return the lowest matched string of length `len` in ascending order of characters, or `None` if no string of this length is matched.
+    pub fn lexicographic_min(&self, len: usize) -> Result<Option<String>, EngineError> {
+        let mut iterator = StringIterator::new(self)?;
+        iterator.compute_completable(len);
+        Ok(iterator.get_extremum_of_length(len, false))
+    }
+
+    /// Return the greatest matched string of length `len` in ascending order of characters, or `None` if no string of this length is matched.
+    pub fn lexicographic_max(&self, len: usize) -> Result<Option<String>, EngineError> {
+        let mut iterator = StringIterator::new(self)?;
+        iterator.compute_completable(len);
+        Ok(iterator.get_extremum_of_length(len, true))
+    }
+}
+
+impl StringIterator {
+    fn new(automaton: &FastAutomaton) -> Result<Self, EngineError> {
+        let automaton = if automaton.deterministic {
+            Cow::Borrowed(automaton)
+        } else {
+            Cow::Owned(automaton.determinize()?)
+        };
+
+        let mut transitions = vec![vec![]; automaton.transitions.len()];
+        for from_state in automaton.transitions_iter() {
+            for (to_state, condition) in
+                automaton.transitions_from_state_enumerate_iter(&from_state)
+            {
+                let range = condition.to_range(&automaton.spanning_set)?;
+                for bounds in range.0.chunks_exact(2) {
+                    transitions[from_state].push((
+                        bounds[0].to_char(),
+                        bounds[1].to_char(),
+                        *to_state,
+                    ));
+                }
+            }
+            transitions[from_state].sort_unstable();
+        }
+
+        let mut reachable_states = IntSet::with_capacity(automaton.get_number_of_states());
+        let mut worklist = VecDeque::from([automaton.start_state]);
+        reachable_states.insert(automaton.start_state);
+        while let Some(state) = worklist.pop_front() {
+            for to_state in automaton.transitions_from_state_iter(&state) {
+                if reachable_states.insert(to_state) {
+                    worklist.push_back(to_state);
+                }
+            }
+        }
+
+        let mut iterator = StringIterator {
+            start_state: automaton.start_state,
+            transitions,
+            reachable_states,
+            completable: vec![automaton.accept_states.clone()],
+            len: 0,
+            stack: vec![],
+            prefix: vec![],
+            exhausted: automaton.is_empty(),
+        };
+        if iterator.completable[0].contains(&iterator.start_state) {
+            iterator.stack.push((iterator.start_state, Some('\0')));
+        }
+        Ok(iterator)
+    }
+
+    /// Extend `completable` up to the given length.
+    fn compute_completable(&mut self, len: usize) {
+        while self.completable.len() <= len {
+            let previous = self.completable.last().unwrap();
+            let completable = self
+                .reachable_states
+                .iter()
+                .filter(|state| {
+                    self.transitions[**state]
+                        .iter()
+                        .any(|(_, _, to_state)| previous.contains(to_state))
+                })
+                .copied()
+                .collect();
+            self.completable.push(completable);
+        }
+    }
+
+    /// Return the lowest character greater or equal to `lower` leading from `state` to a state in `completable[remaining]`.
+    fn find_transition(
+        &self,
+        state: State,
+        lower: Option<char>,
+        remaining: usize,
+    ) -> Option<(char, State)> {
+        let lower = lower?;
+        self.transitions[state]
+            .iter()
+            .find(|(_, to, to_state)| {
+                *to >= lower && self.completable[remaining].contains(to_state)
+            })
+            .map(|(from, _, to_state)| (std::cmp::max(*from, lower), *to_state))
+    }
+
+    /// Return the lowest, or the greatest if `greatest` is set, matched string of the given length, `completable` has to be computed up to this length.
+    fn get_extremum_of_length(&self, len: usize, greatest: bool) -> Option<String> {
+        if self.exhausted || !self.completable[len].contains(&self.start_state) {
+            return None;
+        }
+        let mut string = String::with_capacity(len);
+        let mut state = self.start_state;
+        for remaining in (0..len).rev() {
+            let mut candidates = self.transitions[state]
+                .iter()
+                .filter(|(_, _, to_state)| self.completable[remaining].contains(to_state));
+            let (character, to_state) = if greatest {
+                candidates
+                    .max_by_key(|(_, to, _)| *to)
+                    .map(|(_, to, to_state)| (*to, *to_state))?
+            } else {
+                candidates
+                    .next()
+                    .map(|(from, _, to_state)| (*from, *to_state))?
+            };
+            string.push(character);
+            state = to_state;
+        }
+        Some(string)
+    }
+
+    /// Drop the last character of the current string and move to the next candidate.
+    fn backtrack(&mut self) {
+        if let Some(character) = self.prefix.pop() {
+            if let Some(top) = self.stack.last_mut() {
+                top.1 = Self::next_char(character);
+            }
+        }
+    }
+
+    fn next_char(character: char) -> Option<char> {
+        match character {
+            char::MAX => None,
+            '\u{D7FF}' => Some('\u{E000}'),
+            _ => char::from_u32(character as u32 + 1),
+        }
+    }
+
+    /// Position the iterator right after `last` in shortlex order.
+    fn seek_after(&mut self, last: &str) {
+        if self.exhausted {
+            return;
+        }
+        let last: Vec<char> = last.chars().collect();
+        self.len = last.len();
+        self.compute_completable(self.len);
+        self.stack.clear();
+        self.prefix.clear();
+        if !self.completable[self.len].contains(&self.start_state) {
+            return;
+        }
+        self.stack.push((self.start_state, Some('\0')));
+        for (depth, character) in last.iter().enumerate() {
+            let state = self.stack.last().unwrap().0;
+            match self.find_transition(state, Some(*character), self.len - depth - 1) {
+                Some((next, to_state)) if next == *character => {
+                    self.prefix.push(next);
+                    self.stack.push((to_state, Some('\0')));
+                }
+                _ => {
+                    self.stack.last_mut().unwrap().1 = Some(*character);
+                    return;
+                }
+            }
+        }
+        // `last` itself is matched, skip it.
+        self.stack.pop();
+        self.backtrack();
+    }
+}
+
+impl Iterator for StringIterator {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        loop {
+            let Some(&(state, lower)) = self.stack.last() else {
+                self.len += 1;
+                self.compute_completable(self.len);
+                if self.completable[self.len].is_empty() {
+                    // No reachable state can complete a string of this length, so none can complete a longer one.
+                    self.exhausted = true;
+                    return None;
+                }
+                if self.completable[self.len].contains(&self.start_state) {
+                    self.stack.push((self.start_state, Some('\0')));
+                }
+                continue;
+            };
+
+            let depth = self.prefix.len();
+            if depth == self.len {
+                self.stack.pop();
+                let string = self.prefix.iter().collect();
+                self.backtrack();
+                return Some(string);
+            }
+
+            match self.find_transition(state, lower, self.len - depth - 1) {
+                Some((character, to_state)) => {
+                    self.stack.last_mut().unwrap().1 = Some(character);
+                    self.prefix.push(character);
+                    self.stack.push((to_state, Some('\0')));
+                }
+                None => {
+                    self.stack.pop();
+                    self.backtrack();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::RegularExpression;
+
+    #[test]
+    fn test_strings() -> Result<(), String> {
+        assert_strings("", &[""]);
+        assert_strings("[]", &[]);
+        assert_strings("(b|a|ab)c?", &["a", "b", "ab", "ac", "bc", "abc"]);
+        assert_strings(
+            "[a-c]{2}",
+            &["aa", "ab", "ac", "ba", "bb", "bc", "ca", "cb", "cc"],
+        );
+        assert_strings(
+            "(aa)*b?",
+            &["", "b", "aa", "aab", "aaaa", "aaaab", "aaaaaa"],
+        );
+        assert_strings("x{0,2}(abc|de)*", &["", "x", "de", "xx", "abc", "xde"]);
+        Ok(())
+    }
+
+    fn assert_strings(regex: &str, expected: &[&str]) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let strings: Vec<String> = automaton
+            .strings()
+            .unwrap()
+            .take(expected.len() + 1)
+            .collect();
+        let finite = automaton.get_length().1.is_some() || automaton.is_empty();
+        if finite {
+            assert_eq!(expected, strings, "{regex}");
+        } else {
+            assert_eq!(expected, &strings[..expected.len()], "{regex}");
+        }
+
+        for (i, string) in strings.iter().enumerate() {
+            assert!(automaton.match_string(string));
+            let resumed: Vec<String> = automaton
+                .strings_after(string)
+                .unwrap()
+                .take(strings.len() - i - 1)
+                .collect();
+            assert_eq!(&strings[i + 1..], resumed, "{regex} after {string}");
+        }
+    }
+
+    #[test]
+    fn test_extremums() -> Result<(), String> {
+        assert_extremums(
+            "(b|a|ab)c?",
+            Some("a"),
+            Some("abc"),
+            2,
+            Some("ab"),
+            Some("bc"),
+        );
+        assert_extremums(
+            "[a-c]{2}|x",
+            Some("x"),
+            Some("aa"),
+            2,
+            Some("aa"),
+            Some("cc"),
+        );
+        assert_extremums("(ab)*c", Some("c"), None, 3, Some("abc"), Some("abc"));
+        assert_extremums("(ab)*c", Some("c"), None, 4, None, None);
+        assert_extremums("", Some(""), Some(""), 0, Some(""), Some(""));
+        assert_extremums("[]", None, None, 0, None, None);
+        assert_extremums(".{2}", Some("\0\0"), Some("\0\0"), 1, None, None);
+        assert_extremums(
+            ".",
+            Some("\0"),
+            Some("\0"),
+            1,
+            Some("\0"),
+            Some("\u{10FFFF}"),
+        );
+        Ok(())
+    }
+
+    fn assert_extremums(
+        regex: &str,
+        shortest: Option<&str>,
+        longest: Option<&str>,
+        len: usize,
+        min: Option<&str>,
+        max: Option<&str>,
+    ) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let as_string = |string: Option<&str>| string.map(|string| string.to_string());
+        assert_eq!(
+            as_string(shortest),
+            automaton.shortest_string().unwrap(),
+            "{regex}"
+        );
+        assert_eq!(
+            as_string(longest),
+            automaton.longest_string().unwrap(),
+            "{regex}"
+        );
+        assert_eq!(
+            as_string(min),
+            automaton.lexicographic_min(len).unwrap(),
+            "{regex}"
+        );
+        assert_eq!(
+            as_string(max),
+            automaton.lexicographic_max(len).unwrap(),
+            "{regex}"
+        );
+    }
+
+    #[test]
+    fn test_strings_after_unmatched() -> Result<(), String> {
+        let automaton = RegularExpression::new("(ab|ba|c)*")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let strings: Vec<String> = automaton.strings_after("b").unwrap().take(4).collect();
+        assert_eq!(vec!["c", "ab", "ba", "cc"], strings);
+
+        let strings: Vec<String> = automaton.strings_after("az").unwrap().take(2).collect();
+        assert_eq!(vec!["ba", "cc"], strings);
+
+        let strings: Vec<String> = automaton.strings_after("zz").unwrap().take(2).collect();
+        assert_eq!(vec!["abc", "bac"], strings);
+
+        let mut iterator = RegularExpression::new("a*[^a]")
+            .unwrap()
+            .to_automaton()
+            .unwrap()
+            .strings()
+            .unwrap();
+        assert_eq!(Some("\0".to_string()), iterator.next());
+        assert_eq!(Some("`".to_string()), iterator.nth(95));
+        assert_eq!(Some("b".to_string()), iterator.next());
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
-use std::cmp;
+use std::{borrow::Cow, cmp};
 
-use crate::{execution_profile::ThreadLocalParams, EngineError};
+use crate::{cardinality::BigUint, execution_profile::ThreadLocalParams, EngineError};
 use ahash::AHashSet;
+use num_bigint::RandBigInt;
+use rand::{rngs::StdRng, SeedableRng};
+use regex_charclass::char::Char;
 
 use super::*;
 
@@ -83,6 +86,110 @@ impl FastAutomaton {
 
         Ok(strings)
     }
+
+    /// Draw `count` strings of length at most `max_len` uniformly at random among the matched strings, using a random generator seeded with `rng_seed`.
+    ///
+    /// The draws are independent, so the result can contain duplicates. It is empty if no string of length at most `max_len` is matched.
+    pub fn sample_strings(
+        &self,
+        count: usize,
+        max_len: usize,
+        rng_seed: u64,
+    ) -> Result<Vec<String>, EngineError> {
+        if self.is_empty() || count == 0 {
+            return Ok(vec![]);
+        }
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+
+        let automaton = if self.deterministic {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(self.determinize()?)
+        };
+
+        let number_of_states = automaton.transitions.len();
+        let mut transitions = vec![vec![]; number_of_states];
+        for from_state in automaton.transitions_iter() {
+            for (to_state, condition) in
+                automaton.transitions_from_state_enumerate_iter(&from_state)
+            {
+                let range = condition.to_range(&automaton.spanning_set)?;
+                let cardinality = BigUint::from(range.get_cardinality());
+                transitions[from_state].push((*to_state, range, cardinality));
+            }
+        }
+
+        // completions[state][len] is the number of strings of length `len` leading from `state` to an accept state.
+        let mut completions = vec![Vec::with_capacity(max_len + 1); number_of_states];
+        for state in automaton.transitions_iter() {
+            completions[state].push(BigUint::from(
+                automaton.accept_states.contains(&state) as u32
+            ));
+        }
+        for len in 1..=max_len {
+            execution_profile.assert_not_timed_out()?;
+            for state in automaton.transitions_iter() {
+                let mut completion = BigUint::ZERO;
+                for (to_state, _, cardinality) in &transitions[state] {
+                    completion += cardinality * &completions[*to_state][len - 1];
+                }
+                completions[state].push(completion);
+            }
+        }
+
+        let total: BigUint = completions[automaton.start_state].iter().sum();
+        if total == BigUint::ZERO {
+            return Ok(vec![]);
+        }
+
+        let mut rng = StdRng::seed_from_u64(rng_seed);
+        let mut strings = Vec::with_capacity(count);
+        for _ in 0..count {
+            execution_profile.assert_not_timed_out()?;
+            let mut draw = rng.gen_biguint_below(&total);
+            let mut len = 0;
+            while draw >= completions[automaton.start_state][len] {
+                draw -= &completions[automaton.start_state][len];
+                len += 1;
+            }
+
+            let mut string = String::with_capacity(len);
+            let mut state = automaton.start_state;
+            while len > 0 {
+                let mut draw = rng.gen_biguint_below(&completions[state][len]);
+                for (to_state, range, cardinality) in &transitions[state] {
+                    let completion = &completions[*to_state][len - 1];
+                    let weight = cardinality * completion;
+                    if draw < weight {
+                        let index = u32::try_from(draw / completion)
+                            .expect("The index should be lower than the cardinality of the range.");
+                        string.push(Self::get_nth_character(range, index).to_char());
+                        state = *to_state;
+                        break;
+                    }
+                    draw -= weight;
+                }
+                len -= 1;
+            }
+            strings.push(string);
+        }
+
+        Ok(strings)
+    }
+
+    /// Return the character at the given index of the range, counting in ascending order.
+    fn get_nth_character(range: &Range, mut index: u32) -> Char {
+        for bounds in range.0.chunks_exact(2) {
+            let size = Range::new_from_range(bounds[0]..=bounds[1]).get_cardinality();
+            if index < size {
+                // Skip the surrogate block to get the character whose index among all characters is `index`.
+                let offset = if index < 0xD800 { index } else { index + 0x800 };
+                return bounds[0] + Char::from_u32(offset).expect("Invalid character offset.");
+            }
+            index -= size;
+        }
+        panic!("The index should be lower than the cardinality of the range.")
+    }
 }
 
 #[cfg(test)]
@@ -111,6 +218,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_sample_strings() -> Result<(), String> {
+        assert_sample_strings("[0-9]+[A-Z]*", 200, 10);
+        assert_sample_strings("(ac|ads|a)*", 200, 10);
+        assert_sample_strings(".*", 200, 20);
+        assert_sample_strings("[\\u{D000}-\\u{F000}]{2}", 200, 2);
+        assert_sample_strings("((aad|ads|a)*abc.*uif(aad|ads|x)*|q)", 200, 15);
+
+        let automaton = RegularExpression::new("abc{5}")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(automaton.sample_strings(10, 4, 0).unwrap().is_empty());
+
+        let automaton = RegularExpression::new("[ab]{2}")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let strings = automaton.sample_strings(4000, 2, 42).unwrap();
+        for expected in ["aa", "ab", "ba", "bb"] {
+            let occurrences = strings.iter().filter(|s| *s == expected).count();
+            assert!(
+                (800..1200).contains(&occurrences),
+                "{expected}: {occurrences}"
+            );
+        }
+        assert_eq!(strings, automaton.sample_strings(4000, 2, 42).unwrap());
+        Ok(())
+    }
+
+    fn assert_sample_strings(regex: &str, count: usize, max_len: usize) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let re = Regex::new(&format!("(?s)^{}$", regex)).unwrap();
+
+        let strings = automaton.sample_strings(count, max_len, 7).unwrap();
+        assert_eq!(count, strings.len());
+        for string in strings {
+            assert!(string.chars().count() <= max_len, "'{string}'");
+            assert!(re.is_match(&string), "'{string}'");
+        }
+    }
+
     fn assert_generate_strings(regex: &str, number: usize) {
         println!(":{}", regex);
         let automaton = RegularExpression::new(regex)
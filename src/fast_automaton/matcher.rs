@@ -0,0 +1,237 @@
+use std::{borrow::Cow, cmp};
+
+use crate::EngineError;
+
+use super::*;
+
+const DEAD_STATE: usize = usize::MAX;
+const NO_SYMBOL: usize = usize::MAX;
+
+/// A compiled deterministic matcher built from a [`FastAutomaton`] with [`FastAutomaton::compile_matcher`].
+///
+/// Each character is mapped to its base in the [`SpanningSet`] with a lookup table, then a single transition is followed in a dense table, so matching runs in linear time in the length of the input.
+#[derive(Clone, Debug)]
+pub struct Matcher {
+    /// The non-ASCII characters as `(first, last, symbol)`, sorted by character.
+    classes: Vec<(u32, u32, usize)>,
+    ascii_classes: [usize; 128],
+    number_of_symbols: usize,
+    /// `transitions[state * number_of_symbols + symbol]` is the next state, or `DEAD_STATE`.
+    transitions: Vec<usize>,
+    accept_states: Vec<bool>,
+    start_state: usize,
+}
+
+impl FastAutomaton {
+    /// Compile the automaton into a [`Matcher`], the automaton is determinized first if needed.
+    pub fn compile_matcher(&self) -> Result<Matcher, EngineError> {
+        let automaton = if self.deterministic {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(self.determinize()?)
+        };
+
+        let bases = automaton.spanning_set.get_spanning_ranges_with_rest();
+        let number_of_symbols = bases.len();
+
+        let mut classes = vec![];
+        let mut ascii_classes = [NO_SYMBOL; 128];
+        let mut base_conditions = Vec::with_capacity(number_of_symbols);
+        for (symbol, base) in bases.iter().enumerate() {
+            for bounds in base.0.chunks_exact(2) {
+                let (first, last) = (bounds[0].to_u32(), bounds[1].to_u32());
+                for character in first..=cmp::min(last, 127) {
+                    ascii_classes[character as usize] = symbol;
+                }
+                if last >= 128 {
+                    classes.push((cmp::max(first, 128), last, symbol));
+                }
+            }
+            base_conditions.push(Condition::from_range(base, &automaton.spanning_set)?);
+        }
+        classes.sort_unstable();
+
+        let number_of_states = automaton.transitions.len();
+        let mut transitions = vec![DEAD_STATE; number_of_states * number_of_symbols];
+        let mut accept_states = vec![false; number_of_states];
+        for from_state in automaton.transitions_iter() {
+            accept_states[from_state] = automaton.is_accepted(&from_state);
+            for (to_state, condition) in
+                automaton.transitions_from_state_enumerate_iter(&from_state)
+            {
+                for (symbol, base_condition) in base_conditions.iter().enumerate() {
+                    if condition.has_intersection(base_condition) {
+                        transitions[from_state * number_of_symbols + symbol] = *to_state;
+                    }
+                }
+            }
+        }
+
+        Ok(Matcher {
+            classes,
+            ascii_classes,
+            number_of_symbols,
+            transitions,
+            accept_states,
+            start_state: automaton.start_state,
+        })
+    }
+}
+
+impl Matcher {
+    /// Return `true` if the whole input is matched.
+    pub fn is_match(&self, input: &str) -> bool {
+        let mut state = self.start_state;
+        for character in input.chars() {
+            state = self.next_state(state, character);
+            if state == DEAD_STATE {
+                return false;
+            }
+        }
+        self.accept_states[state]
+    }
+
+    /// Return `true` if the whole byte input is matched, the byte `b` being read as the character `U+00b`.
+    pub fn is_match_bytes(&self, input: &[u8]) -> bool {
+        let mut state = self.start_state;
+        for byte in input {
+            state = self.next_state(state, *byte as char);
+            if state == DEAD_STATE {
+                return false;
+            }
+        }
+        self.accept_states[state]
+    }
+
+    /// Return `true` if a prefix of the input, possibly empty, is matched.
+    pub fn is_match_prefix(&self, input: &str) -> bool {
+        self.longest_match_from(input, 0).is_some()
+    }
+
+    /// Return the byte range of the leftmost-longest substring of the input that is matched.
+    ///
+    /// The automaton is run from each starting position until a match is found, each run stopping as soon as no match is reachable anymore.
+    pub fn find(&self, input: &str) -> Option<std::ops::Range<usize>> {
+        input
+            .char_indices()
+            .map(|(start, _)| start)
+            .chain(std::iter::once(input.len()))
+            .find_map(|start| self.longest_match_from(input, start).map(|end| start..end))
+    }
+
+    /// Return the end of the longest match starting at the given byte position.
+    fn longest_match_from(&self, input: &str, start: usize) -> Option<usize> {
+        let mut state = self.start_state;
+        let mut end = self.accept_states[state].then_some(start);
+        for (position, character) in input[start..].char_indices() {
+            state = self.next_state(state, character);
+            if state == DEAD_STATE {
+                break;
+            }
+            if self.accept_states[state] {
+                end = Some(start + position + character.len_utf8());
+            }
+        }
+        end
+    }
+
+    #[inline]
+    fn next_state(&self, state: usize, character: char) -> usize {
+        let symbol = self.get_symbol(character);
+        if symbol == NO_SYMBOL {
+            return DEAD_STATE;
+        }
+        self.transitions[state * self.number_of_symbols + symbol]
+    }
+
+    #[inline]
+    fn get_symbol(&self, character: char) -> usize {
+        let character = character as u32;
+        if character < 128 {
+            return self.ascii_classes[character as usize];
+        }
+        let index = self
+            .classes
+            .partition_point(|(first, _, _)| *first <= character);
+        if index == 0 {
+            return NO_SYMBOL;
+        }
+        let (_, last, symbol) = self.classes[index - 1];
+        if character <= last {
+            symbol
+        } else {
+            NO_SYMBOL
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::RegularExpression;
+
+    #[test]
+    fn test_matcher() -> Result<(), String> {
+        assert_matcher("(abc|de)*", &["", "abc", "deabcde"], &["a", "abcd", "ed"]);
+        assert_matcher(".*abc", &["abc", "xyzabc", "éàabc"], &["ab", "abcd"]);
+        assert_matcher("[^a]+é", &["bé", "\u{10FFFF}é"], &["é", "aé", "bè"]);
+        assert_matcher("[]", &[], &["", "a"]);
+        assert_matcher(".*", &["", "a", "\u{10FFFF}"], &[]);
+        assert_matcher("(x|y)*z{2}", &["zz", "xyxzz"], &["z", "xyz"]);
+        Ok(())
+    }
+
+    fn assert_matcher(regex: &str, matched: &[&str], not_matched: &[&str]) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let matcher = automaton.compile_matcher().unwrap();
+
+        for string in matched {
+            assert!(matcher.is_match(string), "{regex} {string}");
+            assert!(automaton.match_string(string), "{regex} {string}");
+        }
+        for string in not_matched {
+            assert!(!matcher.is_match(string), "{regex} {string}");
+            assert!(!automaton.match_string(string), "{regex} {string}");
+        }
+    }
+
+    #[test]
+    fn test_find() -> Result<(), String> {
+        let matcher = RegularExpression::new("(ab)+c?")
+            .unwrap()
+            .to_automaton()
+            .unwrap()
+            .compile_matcher()
+            .unwrap();
+
+        assert_eq!(Some(2..7), matcher.find("xxababcab"));
+        assert_eq!(Some(2..6), matcher.find("éababa"));
+        assert_eq!(Some(1..3), matcher.find("aabba"));
+        assert_eq!(None, matcher.find("bbaa"));
+        assert!(matcher.is_match_prefix("ababx"));
+        assert!(!matcher.is_match_prefix("xab"));
+
+        let matcher = RegularExpression::new("a*")
+            .unwrap()
+            .to_automaton()
+            .unwrap()
+            .compile_matcher()
+            .unwrap();
+
+        assert_eq!(Some(0..0), matcher.find("baa"));
+        assert!(matcher.is_match_prefix("b"));
+
+        let matcher = RegularExpression::new_bytes("(?-u)\\xffé")
+            .unwrap()
+            .to_automaton()
+            .unwrap()
+            .compile_matcher()
+            .unwrap();
+
+        assert!(matcher.is_match_bytes(&[0xff, 0xc3, 0xa9]));
+        assert!(!matcher.is_match_bytes("\u{FF}é".as_bytes()));
+        Ok(())
+    }
+}
@@ -16,12 +16,17 @@ mod analyze;
 mod builder;
 pub mod condition;
 mod convert;
+mod enumerate;
 mod generate;
+mod matcher;
 mod operation;
 #[cfg(feature = "serde")]
 mod serializer;
 pub mod spanning_set;
 
+pub use enumerate::StringIterator;
+pub use matcher::Matcher;
+
 /// Represent a finite state automaton.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct FastAutomaton {
@@ -263,31 +268,35 @@ impl FastAutomaton {
         !(state >= self.transitions.len() || self.removed_states.contains(&state))
     }
 
+    /// Return `true` if the given string is matched, by following all the possible paths at once.
+    ///
+    /// To match many strings against the same automaton prefer [`FastAutomaton::compile_matcher`].
     pub fn match_string(&self, input: &str) -> bool {
-        let mut worklist = VecDeque::with_capacity(self.get_number_of_states());
-        worklist.push_back((0, &self.start_state));
-
-        while let Some((position, current_state)) = worklist.pop_back() {
-            if input.len() == position {
-                if self.accept_states.contains(current_state) {
-                    return true;
-                }
-                continue;
-            }
-            let curr_char = input.chars().nth(position).unwrap() as u32;
-            for (to_state, cond) in self.transitions_from_state_enumerate_iter(current_state) {
-                if cond.has_character(&curr_char, &self.spanning_set).unwrap() {
-                    if position + 1 == input.len() {
-                        if self.accept_states.contains(to_state) {
-                            return true;
-                        }
-                    } else {
-                        worklist.push_back((position + 1, to_state));
+        let mut states = IntSet::with_capacity(self.get_number_of_states());
+        states.insert(self.start_state);
+        let mut next_states = IntSet::with_capacity(self.get_number_of_states());
+        for character in input.chars() {
+            let character = character as u32;
+            next_states.clear();
+            for state in &states {
+                for (to_state, cond) in self.transitions_from_state_enumerate_iter(state) {
+                    if !next_states.contains(to_state)
+                        && cond
+                            .has_character(&character, &self.spanning_set)
+                            .unwrap_or(false)
+                    {
+                        next_states.insert(*to_state);
                     }
                 }
             }
+            if next_states.is_empty() {
+                return false;
+            }
+            std::mem::swap(&mut states, &mut next_states);
         }
-        false
+        states
+            .iter()
+            .any(|state| self.accept_states.contains(state))
     }
 
     #[inline]
@@ -315,4 +324,17 @@ mod tests {
         assert!(automaton.is_total());
         Ok(())
     }
+
+    #[test]
+    fn test_match_string_multibyte() -> Result<(), String> {
+        let automaton = crate::regex::RegularExpression::new("é+a?")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(automaton.match_string("é"));
+        assert!(automaton.match_string("ééa"));
+        assert!(!automaton.match_string("ea"));
+        assert!(!automaton.match_string("éaa"));
+        Ok(())
+    }
 }
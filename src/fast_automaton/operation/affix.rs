@@ -0,0 +1,88 @@
+use super::*;
+
+impl FastAutomaton {
+    /// Compute the automaton matching all the prefixes of the strings matched by the current one.
+    pub fn prefixes(&self) -> FastAutomaton {
+        let mut new_automaton = self.get_live_automaton();
+        if new_automaton.is_empty() {
+            return new_automaton;
+        }
+        for state in new_automaton.transitions_vec() {
+            new_automaton.accept(state);
+        }
+        new_automaton
+    }
+
+    /// Compute the automaton matching all the suffixes of the strings matched by the current one.
+    pub fn suffixes(&self) -> FastAutomaton {
+        let mut new_automaton = self.get_live_automaton();
+        if new_automaton.is_empty() {
+            return new_automaton;
+        }
+        let new_start_state = new_automaton.new_state();
+        for state in new_automaton.transitions_vec() {
+            new_automaton.add_epsilon(new_start_state, state);
+        }
+        new_automaton.start_state = new_start_state;
+        new_automaton
+    }
+
+    /// Compute the automaton matching all the factors, i.e. the substrings, of the strings matched by the current one.
+    pub fn factors(&self) -> FastAutomaton {
+        self.prefixes().suffixes()
+    }
+
+    /// Return a copy of the automaton keeping only the states both reachable from the start state and able to reach an accept state.
+    fn get_live_automaton(&self) -> FastAutomaton {
+        let live_states: IntSet<State> = self.get_live_states_ordered().into_iter().collect();
+        if !live_states.iter().any(|state| self.is_accepted(state)) {
+            return Self::new_empty();
+        }
+        let dead_states = self
+            .transitions_iter()
+            .filter(|state| !live_states.contains(state))
+            .collect();
+        let mut new_automaton = self.clone();
+        new_automaton.remove_states(&dead_states);
+        new_automaton
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fast_automaton::FastAutomaton, regex::RegularExpression};
+
+    #[test]
+    fn test_affixes() -> Result<(), String> {
+        assert_affix("abc", FastAutomaton::prefixes, "(a(bc?)?)?");
+        assert_affix("abc", FastAutomaton::suffixes, "((a?b)?c)?");
+        assert_affix("abc", FastAutomaton::factors, "(a|b|c|ab|bc|abc)?");
+        assert_affix("(ab)*c", FastAutomaton::prefixes, "(ab)*(a|c)?");
+        assert_affix("(ab)*c", FastAutomaton::suffixes, "(b?(ab)*c)?");
+        assert_affix("(ab)*c", FastAutomaton::factors, "b?(ab)*(a|c)?");
+        assert_affix("x+y|z", FastAutomaton::prefixes, "(x+y?|z)?");
+        assert_affix("[]", FastAutomaton::prefixes, "[]");
+        assert_affix("[]", FastAutomaton::suffixes, "[]");
+        assert_affix("[]", FastAutomaton::factors, "[]");
+        assert_affix("", FastAutomaton::factors, "");
+        Ok(())
+    }
+
+    fn assert_affix(regex: &str, affix: impl Fn(&FastAutomaton) -> FastAutomaton, expected: &str) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let expected_automaton = RegularExpression::new(expected)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert!(
+            affix(&automaton)
+                .is_equivalent_of(&expected_automaton)
+                .unwrap(),
+            "{regex} {expected}"
+        );
+    }
+}
@@ -78,6 +78,10 @@ impl FastAutomaton {
                     };
                     self.add_transition_to(self.start_state, to_state, &cond);
                 }
+            } else {
+                // The new start state has no incoming edges, so it can be the same state as the start state of `other`
+                new_states.insert(other.start_state, self.start_state);
+                imcomplete_states.insert(self.start_state);
             }
         }
         Ok(imcomplete_states)
@@ -117,16 +121,16 @@ impl FastAutomaton {
                 }
             };
 
+        // The accept states of `other` can already be mapped to states created with the start states, which must be accepted too.
         for &state in &other.accept_states {
-            if other.out_degree(state) == 0 {
-                new_states
+            let new_accept_state = if other.out_degree(state) == 0 {
+                *new_states
                     .entry(state)
-                    .or_insert(accept_state_without_outgoing_edges);
-            } else if new_states.get(&state).is_none() {
-                let new_accept_state = self.new_state();
-                self.accept(new_accept_state);
-                new_states.insert(state, new_accept_state);
-            }
+                    .or_insert(accept_state_without_outgoing_edges)
+            } else {
+                *new_states.entry(state).or_insert_with(|| self.new_state())
+            };
+            self.accept(new_accept_state);
         }
     }
 
@@ -187,7 +191,7 @@ impl FastAutomaton {
 
 #[cfg(test)]
 mod tests {
-    use crate::regex::RegularExpression;
+    use crate::{fast_automaton::FastAutomaton, regex::RegularExpression};
 
     #[test]
     fn test_simple_alternation_regex_1() -> Result<(), String> {
@@ -271,4 +275,69 @@ mod tests {
         assert!(!automaton.match_string("qq"));
         Ok(())
     }
+
+    #[test]
+    fn test_simple_alternation_regex_6() -> Result<(), String> {
+        let automaton = RegularExpression::new("([ab]*|ad*c)")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(automaton.match_string(""));
+        assert!(automaton.match_string("abba"));
+        assert!(automaton.match_string("addc"));
+        assert!(!automaton.match_string("abadc"));
+        assert!(!automaton.match_string("bac"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_alternation_accept_states() -> Result<(), String> {
+        let automaton = RegularExpression::new("a|.*b")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(automaton.match_string("a"));
+        assert!(automaton.match_string("b"));
+        assert!(automaton.match_string("xb"));
+        assert!(!automaton.match_string("ax"));
+
+        let automaton = RegularExpression::new("(|a|b|c|ab|bc|abc)")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(automaton.match_string(""));
+        assert!(automaton.match_string("abc"));
+        assert!(!automaton.match_string("ac"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_alternation_cyclic_start_state() -> Result<(), String> {
+        let automaton1 = RegularExpression::new("[ab]*")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let automaton2 = RegularExpression::new("ad*c")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        for automaton in [
+            automaton1.union(&automaton2).unwrap(),
+            automaton2.union(&automaton1).unwrap(),
+            FastAutomaton::new_empty()
+                .union(&automaton1)
+                .unwrap()
+                .union(&automaton2)
+                .unwrap(),
+        ] {
+            assert!(automaton.match_string(""));
+            assert!(automaton.match_string("abba"));
+            assert!(automaton.match_string("ac"));
+            assert!(automaton.match_string("addc"));
+            assert!(!automaton.match_string("abadc"));
+            assert!(!automaton.match_string("c"));
+        }
+        Ok(())
+    }
 }
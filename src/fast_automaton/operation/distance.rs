@@ -0,0 +1,206 @@
+use crate::{error::EngineError, execution_profile::ThreadLocalParams};
+
+use super::*;
+
+impl FastAutomaton {
+    /// Compute the automaton matching the strings within `k` edits, i.e. insertions, deletions or substitutions of a character, of a string matched by the current one.
+    pub fn within_edit_distance(&self, k: u32) -> Result<FastAutomaton, EngineError> {
+        self.within_distance(k, true)
+    }
+
+    /// Compute the automaton matching the strings within `k` substitutions of a character of a string matched by the current one.
+    pub fn within_hamming_distance(&self, k: u32) -> Result<FastAutomaton, EngineError> {
+        self.within_distance(k, false)
+    }
+
+    /// Build the product of the automaton with the number of edits made so far, allowing insertions and deletions only if `edit` is set.
+    fn within_distance(&self, k: u32, edit: bool) -> Result<FastAutomaton, EngineError> {
+        if self.is_empty() {
+            return Ok(Self::new_empty());
+        }
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+        let k = k as usize;
+        let number_of_states = k
+            .checked_add(1)
+            .and_then(|layers| layers.checked_mul(self.get_number_of_states()));
+        match number_of_states {
+            Some(number_of_states) if number_of_states < execution_profile.max_number_of_states => {
+            }
+            _ => return Err(EngineError::AutomatonHasTooManyStates),
+        }
+
+        // The inserted and substituted characters can be any character, so the spanning set has to cover all of them.
+        let mut automaton = self.clone();
+        automaton.apply_new_spanning_set(&self.spanning_set.merge(&SpanningSet::new_total()))?;
+
+        let mut new_automaton = FastAutomaton::new_empty();
+        new_automaton.spanning_set = automaton.spanning_set.clone();
+        let any_character = Condition::total(&automaton.spanning_set);
+
+        // new_states[edits][state] is the state reached after `edits` edits.
+        let mut new_states: Vec<IntMap<State, State>> = Vec::with_capacity(k + 1);
+        for edits in 0..=k {
+            execution_profile.assert_not_timed_out()?;
+            let mut states = IntMap::with_capacity_and_hasher(
+                automaton.get_number_of_states(),
+                Default::default(),
+            );
+            for state in automaton.transitions_iter() {
+                let new_state = if edits == 0 && state == automaton.start_state {
+                    new_automaton.start_state
+                } else {
+                    new_automaton.new_state()
+                };
+                if automaton.is_accepted(&state) {
+                    new_automaton.accept(new_state);
+                }
+                states.insert(state, new_state);
+            }
+            new_states.push(states);
+        }
+
+        for edits in 0..=k {
+            execution_profile.assert_not_timed_out()?;
+            for from_state in automaton.transitions_iter() {
+                let new_from_state = new_states[edits][&from_state];
+                for (to_state, condition) in
+                    automaton.transitions_from_state_enumerate_iter(&from_state)
+                {
+                    new_automaton.add_transition_to(
+                        new_from_state,
+                        new_states[edits][to_state],
+                        condition,
+                    );
+                    if edits < k {
+                        new_automaton.add_transition_to(
+                            new_from_state,
+                            new_states[edits + 1][to_state],
+                            &any_character,
+                        );
+                    }
+                }
+                if edit && edits < k {
+                    new_automaton.add_transition_to(
+                        new_from_state,
+                        new_states[edits + 1][&from_state],
+                        &any_character,
+                    );
+                }
+            }
+        }
+
+        if edit {
+            // The deletions are added from the last layer so that consecutive deletions are followed.
+            for edits in (0..k).rev() {
+                execution_profile.assert_not_timed_out()?;
+                for from_state in automaton.transitions_iter() {
+                    for to_state in automaton.transitions_from_state_iter(&from_state) {
+                        new_automaton.add_epsilon(
+                            new_states[edits][&from_state],
+                            new_states[edits + 1][&to_state],
+                        );
+                    }
+                }
+            }
+        }
+
+        new_automaton.cyclic = automaton.cyclic;
+        new_automaton.remove_dead_transitions();
+        Ok(new_automaton)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::EngineError, regex::RegularExpression};
+
+    #[test]
+    fn test_within_edit_distance() -> Result<(), String> {
+        let automaton = RegularExpression::new("abc")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let neighborhood = automaton.within_edit_distance(1).unwrap();
+        for string in [
+            "abc", "ab", "bc", "ac", "xbc", "abx", "abcx", "xabc", "axbc", "acb",
+        ] {
+            assert_eq!(
+                string != "acb",
+                neighborhood.match_string(string),
+                "{string}"
+            );
+        }
+        assert!(!neighborhood.match_string("a"));
+        assert!(!neighborhood.match_string("xyc"));
+
+        let neighborhood = automaton.within_edit_distance(2).unwrap();
+        for string in ["", "a", "c", "xyc", "acb", "abcxy", "xaxbc"] {
+            assert_eq!(
+                !string.is_empty(),
+                neighborhood.match_string(string),
+                "{string}"
+            );
+        }
+
+        let neighborhood = automaton.within_edit_distance(3).unwrap();
+        assert!(neighborhood.match_string(""));
+        assert!(neighborhood.match_string("xyz"));
+
+        let automaton = RegularExpression::new("(ab)*")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let neighborhood = automaton.within_edit_distance(1).unwrap();
+        assert!(neighborhood.match_string("abbab"));
+        assert!(neighborhood.match_string("x"));
+        assert!(!neighborhood.match_string("bbbb"));
+        assert!(automaton
+            .within_edit_distance(0)
+            .unwrap()
+            .is_equivalent_of(&automaton)
+            .unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn test_within_hamming_distance() -> Result<(), String> {
+        let automaton = RegularExpression::new("abc|de")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let neighborhood = automaton.within_hamming_distance(1).unwrap();
+        let expected = RegularExpression::new("[^a]bc|a[^b]c|ab[^c]|abc|[^d]e|d[^e]|de")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(neighborhood.is_equivalent_of(&expected).unwrap());
+
+        let neighborhood = automaton.within_hamming_distance(2).unwrap();
+        let expected = RegularExpression::new("..c|.b.|a..|..")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(neighborhood.is_equivalent_of(&expected).unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn test_within_distance_too_many_states() -> Result<(), String> {
+        let automaton = RegularExpression::new("abc")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert_eq!(
+            EngineError::AutomatonHasTooManyStates,
+            automaton.within_edit_distance(u32::MAX).unwrap_err()
+        );
+        assert_eq!(
+            EngineError::AutomatonHasTooManyStates,
+            automaton.within_hamming_distance(u32::MAX).unwrap_err()
+        );
+        Ok(())
+    }
+}
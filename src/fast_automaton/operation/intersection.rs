@@ -1,9 +1,13 @@
 use condition::converter::ConditionConverter;
+use regex_charclass::char::Char;
 
 use crate::{error::EngineError, execution_profile::ThreadLocalParams};
 
 use super::*;
 
+/// For each pair of states reached by the product exploration, the previous pair and the condition leading to it.
+type ProductParents = AHashMap<(State, State), Option<((State, State), Condition)>>;
+
 impl FastAutomaton {
     pub fn intersection(&self, other: &FastAutomaton) -> Result<FastAutomaton, EngineError> {
         if self.is_empty() || other.is_empty() {
@@ -79,6 +83,19 @@ impl FastAutomaton {
         } else if self.is_total() || other.is_total() {
             return Ok(true);
         }
+        Ok(self.get_intersection_witness(other)?.is_some())
+    }
+
+    /// Return the shortest string matched by both automata, or `None` if their intersection is empty.
+    ///
+    /// When several characters are possible at a position, the first printable ASCII character is preferred.
+    pub fn get_intersection_witness(
+        &self,
+        other: &FastAutomaton,
+    ) -> Result<Option<String>, EngineError> {
+        if self.is_empty() || other.is_empty() {
+            return Ok(None);
+        }
         let execution_profile = ThreadLocalParams::get_execution_profile();
 
         let new_spanning_set = self.spanning_set.merge(&other.spanning_set);
@@ -88,31 +105,25 @@ impl FastAutomaton {
         let condition_converter_other_to_new =
             ConditionConverter::new(&other.spanning_set, &new_spanning_set)?;
 
-        let mut new_automaton = FastAutomaton::new_empty();
         let mut worklist =
             VecDeque::with_capacity(self.get_number_of_states() + other.get_number_of_states());
-        let mut new_states: AHashMap<(usize, usize), (usize, usize, usize), _> =
+        let mut parents: ProductParents =
             AHashMap::with_capacity(self.get_number_of_states() + other.get_number_of_states());
 
-        let initial_pair = (
-            new_automaton.start_state,
-            self.start_state,
-            other.start_state,
-        );
-
+        let initial_pair = (self.start_state, other.start_state);
         worklist.push_back(initial_pair);
-        new_states.insert((self.start_state, other.start_state), initial_pair);
+        parents.insert(initial_pair, None);
 
         while let Some(p) = worklist.pop_front() {
             execution_profile.assert_not_timed_out()?;
-            if self.accept_states.contains(&p.1) && other.accept_states.contains(&p.2) {
-                return Ok(true);
+            if self.accept_states.contains(&p.0) && other.accept_states.contains(&p.1) {
+                return Ok(Some(Self::build_witness(p, &parents, &new_spanning_set)?));
             }
 
             let transitions_1 =
-                self.get_projected_transitions(p.1, &condition_converter_self_to_new)?;
+                self.get_projected_transitions(p.0, &condition_converter_self_to_new)?;
             let transitions_2 =
-                other.get_projected_transitions(p.2, &condition_converter_other_to_new)?;
+                other.get_projected_transitions(p.1, &condition_converter_other_to_new)?;
 
             for (n1, condition_1) in transitions_1 {
                 for (n2, condition_2) in &transitions_2 {
@@ -120,24 +131,38 @@ impl FastAutomaton {
                     if intersection.is_empty() {
                         continue;
                     }
-                    let k = (n1, *n2);
-                    let r = match new_states.get(&k) {
-                        Some(new_r) => *new_r,
-                        None => {
-                            let new_r = (new_automaton.new_state(), n1, *n2);
-                            worklist.push_back(new_r);
-                            new_states.insert(k, new_r);
-                            new_r
-                        }
-                    };
-                    new_automaton.add_transition_to(p.0, r.0, &intersection);
+                    if let Entry::Vacant(v) = parents.entry((n1, *n2)) {
+                        v.insert(Some((p, intersection)));
+                        worklist.push_back((n1, *n2));
+                    }
                 }
             }
         }
-        Ok(false)
+        Ok(None)
     }
 
-    fn get_projected_transitions(
+    fn build_witness(
+        mut pair: (State, State),
+        parents: &ProductParents,
+        spanning_set: &SpanningSet,
+    ) -> Result<String, EngineError> {
+        let printable = Range::new_from_range(Char::new(' ')..=Char::new('~'));
+        let mut characters = vec![];
+        while let Some(Some((parent, condition))) = parents.get(&pair) {
+            let range = condition.to_range(spanning_set)?;
+            let preferred = range.intersection(&printable);
+            let character = preferred
+                .iter()
+                .next()
+                .or_else(|| range.iter().next())
+                .ok_or(EngineError::ConditionInvalidRange)?;
+            characters.push(character.to_char());
+            pair = *parent;
+        }
+        Ok(characters.into_iter().rev().collect())
+    }
+
+    pub(super) fn get_projected_transitions(
         &self,
         state: State,
         condition_converter: &ConditionConverter,
@@ -237,6 +262,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_intersection_witness() -> Result<(), String> {
+        assert_intersection_witness("(abc|ac|aaa)", "(abcd|ac|aba)", Some("ac"));
+        assert_intersection_witness(".*abc", "a.*", Some("abc"));
+        assert_intersection_witness("x*", "(xxx)+", Some("xxx"));
+        assert_intersection_witness("a*", "b*", Some(""));
+        assert_intersection_witness("a+", "b+", None);
+        assert_intersection_witness("[\\x00-\\x10A]", ".", Some("A"));
+        assert_intersection_witness("[\\x00-\\x10]", ".", Some("\x00"));
+        Ok(())
+    }
+
+    fn assert_intersection_witness(regex1: &str, regex2: &str, expected: Option<&str>) {
+        let automaton1 = RegularExpression::new(regex1)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let automaton2 = RegularExpression::new(regex2)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let witness = automaton1.get_intersection_witness(&automaton2).unwrap();
+        assert_eq!(expected, witness.as_deref(), "{regex1} & {regex2}");
+        assert_eq!(
+            expected.is_some(),
+            automaton1.has_intersection(&automaton2).unwrap()
+        );
+    }
+
     #[test]
     fn test_complex_intersection_regex_2() -> Result<(), String> {
         let automaton1 = RegularExpression::new("(?:[a-z0-9]+(?:\\.[a-z0-9]+)*|\"(?:[\\x01-\\x08\\x0b\\x0c\\x0e-\\x1f\\x21\\x23-\\x5b\\x5d-\\x7f]|\\\\[\\x01-\\x09\\x0b\\x0c\\x0e-\\x7f])*\")@(?:(?:[a-z0-9](?:[a-z0-9-]*[a-z0-9])?\\.)+[a-z0-9](?:[a-z0-9-]*[a-z0-9])?|\\[(?:(?:(2(5[0-5]|[0-4][0-9])|1[0-9][0-9]|[1-9]?[0-9]))\\.){3}(?:(2(5[0-5]|[0-4][0-9])|1[0-9][0-9]|[1-9]?[0-9])|[a-z0-9-]*[a-z0-9]:(?:[\\x01-\\x08\\x0b\\x0c\\x0e-\\x1f\\x21-\\x5a\\x53-\\x7f]|\\\\[\\x01-\\x09\\x0b\\x0c\\x0e-\\x7f])+)\\])")
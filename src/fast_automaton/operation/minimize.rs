@@ -0,0 +1,253 @@
+use std::borrow::Cow;
+
+use ahash::AHashSet;
+
+use crate::{error::EngineError, execution_profile::ThreadLocalParams};
+
+use super::*;
+
+impl FastAutomaton {
+    /// Compute the minimal deterministic automaton recognizing the same language, using Hopcroft's partition refinement.
+    ///
+    /// The symbols of the alphabet are the bases of the [`SpanningSet`], the automaton is determinized first if needed.
+    pub fn minimize(&self) -> Result<FastAutomaton, EngineError> {
+        if self.is_empty() {
+            return Ok(Self::new_empty());
+        } else if self.is_total() {
+            return Ok(Self::new_total());
+        }
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+
+        let automaton = if self.deterministic {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(self.determinize()?)
+        };
+
+        let states = automaton.get_live_states_ordered();
+        if !states.iter().any(|state| automaton.is_accepted(state)) {
+            return Ok(Self::new_empty());
+        }
+        let number_of_states = states.len();
+        let mut state_index: IntMap<State, usize> =
+            IntMap::with_capacity_and_hasher(number_of_states, Default::default());
+        for (index, state) in states.iter().enumerate() {
+            state_index.insert(*state, index);
+        }
+
+        // The index `number_of_states` is a virtual sink state collecting the missing transitions.
+        let sink = number_of_states;
+        let number_of_symbols = automaton.spanning_set.spanning_ranges_with_rest_len();
+        let mut inverse_transitions = vec![vec![vec![]; number_of_states + 1]; number_of_symbols];
+        for (from_index, from_state) in states.iter().enumerate() {
+            let mut targets = vec![sink; number_of_symbols];
+            for (to_state, condition) in automaton.transitions_from_state_enumerate_iter(from_state)
+            {
+                if let Some(&to_index) = state_index.get(to_state) {
+                    for (symbol, has) in condition.get_bits().into_iter().enumerate() {
+                        if has {
+                            targets[symbol] = to_index;
+                        }
+                    }
+                }
+            }
+            for (symbol, to_index) in targets.into_iter().enumerate() {
+                inverse_transitions[symbol][to_index].push(from_index);
+            }
+        }
+        for symbol_inverse_transitions in inverse_transitions.iter_mut() {
+            symbol_inverse_transitions[sink].push(sink);
+        }
+
+        let (accepting, rejecting): (Vec<usize>, Vec<usize>) =
+            (0..=sink).partition(|&index| index != sink && automaton.is_accepted(&states[index]));
+
+        let mut blocks = vec![accepting];
+        let mut block_of = vec![0; number_of_states + 1];
+        if !rejecting.is_empty() {
+            for &index in &rejecting {
+                block_of[index] = 1;
+            }
+            blocks.push(rejecting);
+        }
+
+        let mut worklist = VecDeque::with_capacity(number_of_symbols * blocks.len());
+        let mut in_worklist = AHashSet::with_capacity(number_of_symbols * blocks.len());
+        let smallest_block = if blocks.len() > 1 && blocks[1].len() < blocks[0].len() {
+            1
+        } else {
+            0
+        };
+        for symbol in 0..number_of_symbols {
+            worklist.push_back((smallest_block, symbol));
+            in_worklist.insert((smallest_block, symbol));
+        }
+
+        let mut marked: IntMap<usize, Vec<usize>> = IntMap::default();
+        while let Some((splitter, symbol)) = worklist.pop_front() {
+            execution_profile.assert_not_timed_out()?;
+            in_worklist.remove(&(splitter, symbol));
+
+            marked.clear();
+            for &to_index in &blocks[splitter] {
+                for &from_index in &inverse_transitions[symbol][to_index] {
+                    marked
+                        .entry(block_of[from_index])
+                        .or_default()
+                        .push(from_index);
+                }
+            }
+
+            for (&block, predecessors) in &marked {
+                if predecessors.len() == blocks[block].len() {
+                    continue;
+                }
+                let new_block = blocks.len();
+                for &index in predecessors {
+                    block_of[index] = new_block;
+                }
+                let (moved, kept): (Vec<usize>, Vec<usize>) = blocks[block]
+                    .iter()
+                    .partition(|&&index| block_of[index] == new_block);
+                blocks[block] = kept;
+                blocks.push(moved);
+
+                for symbol in 0..number_of_symbols {
+                    let to_add = if in_worklist.contains(&(block, symbol))
+                        || blocks[new_block].len() <= blocks[block].len()
+                    {
+                        new_block
+                    } else {
+                        block
+                    };
+                    if in_worklist.insert((to_add, symbol)) {
+                        worklist.push_back((to_add, symbol));
+                    }
+                }
+            }
+        }
+
+        let mut new_automaton = FastAutomaton::new_empty();
+        new_automaton.spanning_set = automaton.spanning_set.clone();
+        new_automaton.cyclic = automaton.cyclic;
+
+        let mut new_states: IntMap<usize, State> =
+            IntMap::with_capacity_and_hasher(blocks.len(), Default::default());
+        new_states.insert(block_of[0], new_automaton.start_state);
+        let mut worklist = VecDeque::from([block_of[0]]);
+        while let Some(block) = worklist.pop_front() {
+            let new_from_state = new_states[&block];
+            let representative = states[blocks[block][0]];
+            if automaton.is_accepted(&representative) {
+                new_automaton.accept(new_from_state);
+            }
+            for (to_state, condition) in
+                automaton.transitions_from_state_enumerate_iter(&representative)
+            {
+                let to_block = if let Some(to_index) = state_index.get(to_state) {
+                    block_of[*to_index]
+                } else {
+                    continue;
+                };
+                if to_block == block_of[sink] {
+                    continue;
+                }
+                let new_to_state = match new_states.entry(to_block) {
+                    Entry::Occupied(o) => *o.get(),
+                    Entry::Vacant(v) => {
+                        let new_state = new_automaton.new_state();
+                        worklist.push_back(to_block);
+                        v.insert(new_state);
+                        new_state
+                    }
+                };
+                new_automaton.add_transition_to(new_from_state, new_to_state, condition);
+            }
+        }
+
+        Ok(new_automaton)
+    }
+
+    /// Return the states that are both reachable from the start state and able to reach an accept state, the start state first.
+    pub(super) fn get_live_states_ordered(&self) -> Vec<State> {
+        let co_reachable_states = self.get_reacheable_states();
+
+        let mut states = Vec::with_capacity(co_reachable_states.len());
+        let mut seen = IntSet::with_capacity(co_reachable_states.len());
+        let mut worklist = VecDeque::from([self.start_state]);
+        seen.insert(self.start_state);
+        while let Some(state) = worklist.pop_front() {
+            states.push(state);
+            for to_state in self.transitions_from_state_iter(&state) {
+                if co_reachable_states.contains(&to_state) && seen.insert(to_state) {
+                    worklist.push_back(to_state);
+                }
+            }
+        }
+        states
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fast_automaton::FastAutomaton, regex::RegularExpression};
+
+    #[test]
+    fn test_minimize() -> Result<(), String> {
+        assert_minimize("abc", 4);
+        assert_minimize("(abc|abd)", 4);
+        assert_minimize("(a|b)*abb", 4);
+        assert_minimize(".*abc", 4);
+        assert_minimize("(ab|cd)*", 3);
+        assert_minimize("a{2,5}", 6);
+        assert_minimize("(aad|ads|a)*abc.*def", 10);
+        assert_minimize("", 1);
+        assert_minimize("[]", 1);
+        assert_minimize(".*", 1);
+        Ok(())
+    }
+
+    fn assert_minimize(regex: &str, expected_number_of_states: usize) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let minimized = automaton.minimize().unwrap();
+        assert!(minimized.is_determinitic());
+        assert_eq!(
+            expected_number_of_states,
+            minimized.get_number_of_states(),
+            "{regex}"
+        );
+        assert!(automaton.is_equivalent_of(&minimized).unwrap());
+
+        let minimized_again = minimized.minimize().unwrap();
+        assert_eq!(
+            minimized.get_number_of_states(),
+            minimized_again.get_number_of_states()
+        );
+    }
+
+    #[test]
+    fn test_minimize_after_operation() -> Result<(), String> {
+        let automaton1 = RegularExpression::new("x*")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let automaton2 = RegularExpression::new("(xxx)*")
+            .unwrap()
+            .to_automaton()
+            .unwrap()
+            .determinize()
+            .unwrap();
+
+        let subtraction = automaton1.subtraction(&automaton2).unwrap();
+        let minimized = subtraction.minimize().unwrap();
+
+        assert_eq!(3, minimized.get_number_of_states());
+        assert!(subtraction.is_equivalent_of(&minimized).unwrap());
+        assert!(FastAutomaton::new_empty().minimize().unwrap().is_empty());
+        Ok(())
+    }
+}
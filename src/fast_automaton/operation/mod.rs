@@ -4,16 +4,27 @@ use ahash::AHasher;
 
 use super::*;
 
+mod affix;
 mod alternation;
 mod concatenate;
 mod determinize;
+mod distance;
 mod intersection;
+mod minimize;
+mod quotient;
+mod reverse;
+mod substitute;
 mod subtraction;
+mod transduce;
 
 impl FastAutomaton {
     pub fn remove_dead_transitions(&mut self) {
         if !self.is_empty() {
             let reacheable_states = self.get_reacheable_states();
+            if !reacheable_states.contains(&self.start_state) {
+                self.make_empty();
+                return;
+            }
 
             let mut dead_states = IntSet::default();
             for from_state in self.transitions_iter() {
@@ -30,7 +41,7 @@ impl FastAutomaton {
 
 #[cfg(test)]
 mod tests {
-    use crate::regex::RegularExpression;
+    use crate::{fast_automaton::FastAutomaton, regex::RegularExpression};
 
     #[test]
     fn test_remove_dead_states() -> Result<(), String> {
@@ -47,4 +58,15 @@ mod tests {
         assert_eq!(3, intersection.get_reacheable_states().len());
         Ok(())
     }
+
+    #[test]
+    fn test_remove_dead_start_state() -> Result<(), String> {
+        let mut automaton = FastAutomaton::new_empty();
+        let accept_state = automaton.new_state();
+        automaton.accept(accept_state);
+
+        automaton.remove_dead_transitions();
+        assert!(automaton.is_empty());
+        Ok(())
+    }
 }
@@ -0,0 +1,155 @@
+use ahash::AHashSet;
+use condition::converter::ConditionConverter;
+
+use crate::{error::EngineError, execution_profile::ThreadLocalParams};
+
+use super::*;
+
+impl FastAutomaton {
+    /// Compute the left quotient of the current automaton by the given one: the strings `w` such that `uw` is matched by the current automaton for some `u` matched by `prefixes`.
+    pub fn left_quotient(&self, prefixes: &FastAutomaton) -> Result<FastAutomaton, EngineError> {
+        if self.is_empty() || prefixes.is_empty() {
+            return Ok(Self::new_empty());
+        }
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+
+        let new_spanning_set = self.spanning_set.merge(&prefixes.spanning_set);
+
+        let condition_converter_self_to_new =
+            ConditionConverter::new(&self.spanning_set, &new_spanning_set)?;
+        let condition_converter_prefixes_to_new =
+            ConditionConverter::new(&prefixes.spanning_set, &new_spanning_set)?;
+
+        let mut worklist =
+            VecDeque::with_capacity(self.get_number_of_states() + prefixes.get_number_of_states());
+        let mut seen =
+            AHashSet::with_capacity(self.get_number_of_states() + prefixes.get_number_of_states());
+        let mut new_start_states = IntSet::default();
+
+        let initial_pair = (self.start_state, prefixes.start_state);
+        worklist.push_back(initial_pair);
+        seen.insert(initial_pair);
+
+        while let Some(p) = worklist.pop_front() {
+            execution_profile.assert_not_timed_out()?;
+            execution_profile.assert_max_number_of_states(seen.len())?;
+            if prefixes.accept_states.contains(&p.1) {
+                new_start_states.insert(p.0);
+            }
+
+            let transitions_1 =
+                self.get_projected_transitions(p.0, &condition_converter_self_to_new)?;
+            let transitions_2 =
+                prefixes.get_projected_transitions(p.1, &condition_converter_prefixes_to_new)?;
+
+            for (n1, condition_1) in &transitions_1 {
+                for (n2, condition_2) in &transitions_2 {
+                    if condition_1.has_intersection(condition_2) && seen.insert((*n1, *n2)) {
+                        worklist.push_back((*n1, *n2));
+                    }
+                }
+            }
+        }
+
+        if new_start_states.is_empty() {
+            return Ok(Self::new_empty());
+        }
+        let mut new_automaton = self.clone();
+        let new_start_state = new_automaton.new_state();
+        for state in new_start_states {
+            new_automaton.add_epsilon(new_start_state, state);
+        }
+        new_automaton.start_state = new_start_state;
+        new_automaton.remove_dead_transitions();
+        Ok(new_automaton)
+    }
+
+    /// Compute the right quotient of the current automaton by the given one: the strings `w` such that `wu` is matched by the current automaton for some `u` matched by `suffixes`.
+    pub fn right_quotient(&self, suffixes: &FastAutomaton) -> Result<FastAutomaton, EngineError> {
+        Ok(self.reverse().left_quotient(&suffixes.reverse())?.reverse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::EngineError,
+        execution_profile::{ExecutionProfile, ThreadLocalParams},
+        fast_automaton::FastAutomaton,
+        regex::RegularExpression,
+    };
+
+    #[test]
+    fn test_quotients() -> Result<(), String> {
+        assert_quotient(
+            "/api/v[0-9]+/(users|items)/[0-9]+",
+            FastAutomaton::left_quotient,
+            "/api/v[0-9]+/",
+            "(users|items)/[0-9]+",
+        );
+        assert_quotient(
+            "/api/v[0-9]+/(users|items)/[0-9]+",
+            FastAutomaton::left_quotient,
+            "/api/v1",
+            "[0-9]*/(users|items)/[0-9]+",
+        );
+        assert_quotient("(ab)*c", FastAutomaton::left_quotient, "a", "b(ab)*c");
+        assert_quotient("(ab)*c", FastAutomaton::left_quotient, "(ab)*", "(ab)*c");
+        assert_quotient("abc", FastAutomaton::left_quotient, "x", "[]");
+        assert_quotient("abc", FastAutomaton::left_quotient, "abc", "");
+        assert_quotient("(ab)*c", FastAutomaton::right_quotient, "c", "(ab)*");
+        assert_quotient("(ab)*c", FastAutomaton::right_quotient, "bc", "(ab)*a");
+        assert_quotient("a+b+", FastAutomaton::right_quotient, "b", "a+b*");
+        assert_quotient("abc", FastAutomaton::right_quotient, "[]", "[]");
+        Ok(())
+    }
+
+    #[test]
+    fn test_left_quotient_too_many_states() -> Result<(), String> {
+        let automaton = RegularExpression::new(".*a.{20}")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let prefixes = RegularExpression::new(".*b.{20}")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        ThreadLocalParams::init_profile(&ExecutionProfile {
+            max_number_of_states: 100,
+            start_execution_time: None,
+            execution_timeout: 1500,
+            max_number_of_terms: 50,
+        });
+        assert_eq!(
+            EngineError::AutomatonHasTooManyStates,
+            automaton.left_quotient(&prefixes).unwrap_err()
+        );
+        Ok(())
+    }
+
+    fn assert_quotient(
+        regex: &str,
+        quotient: impl Fn(&FastAutomaton, &FastAutomaton) -> Result<FastAutomaton, EngineError>,
+        by: &str,
+        expected: &str,
+    ) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let by_automaton = RegularExpression::new(by).unwrap().to_automaton().unwrap();
+        let expected_automaton = RegularExpression::new(expected)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert!(
+            quotient(&automaton, &by_automaton)
+                .unwrap()
+                .is_equivalent_of(&expected_automaton)
+                .unwrap(),
+            "{regex} {by} {expected}"
+        );
+    }
+}
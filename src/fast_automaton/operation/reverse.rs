@@ -0,0 +1,73 @@
+use super::*;
+
+impl FastAutomaton {
+    /// Compute the automaton matching the reversed strings of the current one.
+    ///
+    /// The transitions are flipped and a fresh start state is linked to the former accept states, the former start state becomes the only accept state.
+    pub fn reverse(&self) -> FastAutomaton {
+        if self.is_empty() {
+            return Self::new_empty();
+        }
+        let mut new_automaton = FastAutomaton::new_empty();
+        new_automaton.spanning_set = self.spanning_set.clone();
+
+        let mut new_states: IntMap<State, State> =
+            IntMap::with_capacity_and_hasher(self.get_number_of_states(), Default::default());
+        for state in self.transitions_iter() {
+            new_states.insert(state, new_automaton.new_state());
+        }
+
+        for from_state in self.transitions_iter() {
+            for (to_state, condition) in self.transitions_from_state_enumerate_iter(&from_state) {
+                new_automaton.add_transition_to(
+                    new_states[to_state],
+                    new_states[&from_state],
+                    condition,
+                );
+            }
+        }
+
+        new_automaton.accept(new_states[&self.start_state]);
+        for accept_state in &self.accept_states {
+            new_automaton.add_epsilon(new_automaton.start_state, new_states[accept_state]);
+        }
+        new_automaton.cyclic = self.cyclic;
+        new_automaton.remove_dead_transitions();
+        new_automaton
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::RegularExpression;
+
+    #[test]
+    fn test_reverse() -> Result<(), String> {
+        assert_reverse("abc", "cba");
+        assert_reverse("(abc|de)*f", "f(cba|ed)*");
+        assert_reverse("a+b?|cd", "b?a+|dc");
+        assert_reverse("(a|b)*abb", "bba(a|b)*");
+        assert_reverse(".*ab(c{2,4}|d)", "(c{2,4}|d)ba.*");
+        assert_reverse("", "");
+        assert_reverse("[]", "[]");
+        Ok(())
+    }
+
+    fn assert_reverse(regex: &str, expected: &str) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let expected = RegularExpression::new(expected)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let reversed = automaton.reverse();
+        assert!(reversed.is_equivalent_of(&expected).unwrap(), "{regex}");
+        assert!(
+            reversed.reverse().is_equivalent_of(&automaton).unwrap(),
+            "{regex}"
+        );
+    }
+}
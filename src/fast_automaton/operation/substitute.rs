@@ -0,0 +1,270 @@
+use crate::error::EngineError;
+
+use super::*;
+
+impl FastAutomaton {
+    /// Compute the automaton matching the images of the strings matched by the current one through the given character mapping.
+    ///
+    /// The mapping is applied to each range of the [`SpanningSet`], a transition on several ranges then leads to the union of their images.
+    pub fn map_chars(
+        &self,
+        mapping: impl Fn(&Range) -> Range,
+    ) -> Result<FastAutomaton, EngineError> {
+        if self.is_empty() {
+            return Ok(Self::new_empty());
+        }
+        let bases: Vec<Range> = self.spanning_set.get_spanning_ranges().cloned().collect();
+        let images: Vec<Range> = bases.iter().map(mapping).collect();
+
+        let mut new_automaton = FastAutomaton::new_empty();
+        new_automaton.spanning_set = SpanningSet::compute_spanning_set(&images);
+
+        let new_states = self.copy_states_into(&mut new_automaton, true);
+        self.copy_accept_states_into(&mut new_automaton, &new_states);
+        for from_state in self.transitions_iter() {
+            for (to_state, condition) in self.transitions_from_state_enumerate_iter(&from_state) {
+                let range = condition.to_range(&self.spanning_set)?;
+                let image = bases
+                    .iter()
+                    .zip(&images)
+                    .filter(|(base, _)| range.has_intersection(base))
+                    .fold(Range::empty(), |image, (_, base_image)| {
+                        image.union(base_image)
+                    });
+                new_automaton.add_transition_to(
+                    new_states[&from_state],
+                    new_states[to_state],
+                    &Condition::from_range(&image, &new_automaton.spanning_set)?,
+                );
+            }
+        }
+
+        new_automaton.cyclic = self.cyclic;
+        new_automaton.remove_dead_transitions();
+        Ok(new_automaton)
+    }
+
+    /// Compute the automaton where each occurrence of a character of `char_class` in the strings matched by the current one is replaced by a string matched by `language`.
+    pub fn substitute(
+        &self,
+        char_class: &Range,
+        language: &FastAutomaton,
+    ) -> Result<FastAutomaton, EngineError> {
+        if self.is_empty() {
+            return Ok(Self::new_empty());
+        }
+        let mut ranges: Vec<Range> = self.spanning_set.get_spanning_ranges().cloned().collect();
+        ranges.extend(language.spanning_set.get_spanning_ranges().cloned());
+        ranges.push(char_class.clone());
+
+        let mut new_automaton = FastAutomaton::new_empty();
+        new_automaton.spanning_set = SpanningSet::compute_spanning_set(&ranges);
+
+        let new_states = self.copy_states_into(&mut new_automaton, true);
+        self.copy_accept_states_into(&mut new_automaton, &new_states);
+        let mut epsilons: IntMap<State, IntSet<State>> = IntMap::default();
+        for from_state in self.transitions_iter() {
+            let new_from_state = new_states[&from_state];
+            for (to_state, condition) in self.transitions_from_state_enumerate_iter(&from_state) {
+                let new_to_state = new_states[to_state];
+                let range = condition.to_range(&self.spanning_set)?;
+
+                let kept_range = range.difference(char_class);
+                new_automaton.add_transition_to(
+                    new_from_state,
+                    new_to_state,
+                    &Condition::from_range(&kept_range, &new_automaton.spanning_set)?,
+                );
+
+                if language.is_empty() || !range.has_intersection(char_class) {
+                    continue;
+                }
+                // Insert a copy of `language` between the two states, the transitions leaving its start state and reaching its accept states are duplicated from and to them.
+                let copy_states = language.copy_states_into(&mut new_automaton, false);
+                for language_from_state in language.transitions_iter() {
+                    for (language_to_state, language_condition) in
+                        language.transitions_from_state_enumerate_iter(&language_from_state)
+                    {
+                        let condition = Condition::from_range(
+                            &language_condition.to_range(&language.spanning_set)?,
+                            &new_automaton.spanning_set,
+                        )?;
+                        let mut from_states = vec![copy_states[&language_from_state]];
+                        if language_from_state == language.start_state {
+                            from_states.push(new_from_state);
+                        }
+                        let mut to_states = vec![copy_states[language_to_state]];
+                        if language.is_accepted(language_to_state) {
+                            to_states.push(new_to_state);
+                        }
+                        for from in &from_states {
+                            for to in &to_states {
+                                new_automaton.add_transition_to(*from, *to, &condition);
+                            }
+                        }
+                    }
+                }
+                if language.is_accepted(&language.start_state) {
+                    epsilons
+                        .entry(new_from_state)
+                        .or_default()
+                        .insert(new_to_state);
+                }
+            }
+        }
+
+        // The empty string is matched by `language`, the states are linked through the closure of the empty transitions.
+        let transitions: IntMap<State, Vec<(State, Condition)>> = new_automaton
+            .transitions_iter()
+            .map(|state| {
+                (
+                    state,
+                    new_automaton.transitions_from_state_enumerate_vec(&state),
+                )
+            })
+            .collect();
+        let accept_states = new_automaton.accept_states.clone();
+        for (from_state, targets) in &epsilons {
+            let mut closure: IntSet<State> = targets.clone();
+            let mut worklist: VecDeque<State> = targets.iter().copied().collect();
+            while let Some(state) = worklist.pop_front() {
+                for next_state in epsilons.get(&state).into_iter().flatten() {
+                    if closure.insert(*next_state) {
+                        worklist.push_back(*next_state);
+                    }
+                }
+            }
+            for state in closure {
+                if accept_states.contains(&state) {
+                    new_automaton.accept(*from_state);
+                }
+                for (to_state, condition) in &transitions[&state] {
+                    new_automaton.add_transition_to(*from_state, *to_state, condition);
+                }
+            }
+        }
+
+        new_automaton.cyclic = self.cyclic || language.cyclic;
+        new_automaton.remove_dead_transitions();
+        Ok(new_automaton)
+    }
+
+    /// Add a new state to the given automaton for each state of the current one, the start state is mapped to the start state of the given automaton if `reuse_start_state` is set.
+    fn copy_states_into(
+        &self,
+        new_automaton: &mut FastAutomaton,
+        reuse_start_state: bool,
+    ) -> IntMap<State, State> {
+        let mut new_states =
+            IntMap::with_capacity_and_hasher(self.get_number_of_states(), Default::default());
+        for state in self.transitions_iter() {
+            let new_state = if reuse_start_state && state == self.start_state {
+                new_automaton.start_state
+            } else {
+                new_automaton.new_state()
+            };
+            new_states.insert(state, new_state);
+        }
+        new_states
+    }
+
+    /// Accept the states of the given automaton mapped from the accept states of the current one.
+    fn copy_accept_states_into(
+        &self,
+        new_automaton: &mut FastAutomaton,
+        new_states: &IntMap<State, State>,
+    ) {
+        for state in &self.accept_states {
+            new_automaton.accept(new_states[state]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex_charclass::char::Char;
+
+    use crate::{regex::RegularExpression, Range};
+
+    #[test]
+    fn test_map_chars() -> Result<(), String> {
+        let lowercase = |range: &Range| {
+            let uppercase = Range::new_from_range(Char::new('A')..=Char::new('Z'));
+            let mut image = range.difference(&uppercase);
+            for character in range.intersection(&uppercase).iter() {
+                let lower = Char::new(character.to_char().to_ascii_lowercase());
+                image = image.union(&Range::new_from_range(lower..=lower));
+            }
+            image
+        };
+
+        assert_map_chars("Hello(World|THERE)", lowercase, "hello(world|there)");
+        assert_map_chars("[A-Z][a-z]+", lowercase, "[a-z]{2,}");
+        assert_map_chars(
+            "(ab|c)*",
+            |_| Range::new_from_range(Char::new('x')..=Char::new('x')),
+            "x*",
+        );
+        assert_map_chars("a[bc]", |_| Range::empty(), "[]");
+        Ok(())
+    }
+
+    fn assert_map_chars(regex: &str, mapping: impl Fn(&Range) -> Range, expected: &str) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let expected_automaton = RegularExpression::new(expected)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert!(
+            automaton
+                .map_chars(mapping)
+                .unwrap()
+                .is_equivalent_of(&expected_automaton)
+                .unwrap(),
+            "{regex} {expected}"
+        );
+    }
+
+    #[test]
+    fn test_substitute() -> Result<(), String> {
+        assert_substitute("v1\\.0", "\\.", "\\\\\\.", "v1\\\\\\.0");
+        assert_substitute("(ab)*", "a", "x+", "(x+b)*");
+        assert_substitute("abc", "b", "(de)?", "a(de)?c");
+        assert_substitute("ab*c", "b", "x?", "ax*c");
+        assert_substitute("a[bc]d", "[bc]", "[]", "[]");
+        assert_substitute("ab", "z", "xyz", "ab");
+        Ok(())
+    }
+
+    fn assert_substitute(regex: &str, char_class: &str, language: &str, expected: &str) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let char_class = match RegularExpression::new(char_class).unwrap() {
+            RegularExpression::Character(range) => range,
+            _ => unreachable!(),
+        };
+        let language_automaton = RegularExpression::new(language)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let expected_automaton = RegularExpression::new(expected)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert!(
+            automaton
+                .substitute(&char_class, &language_automaton)
+                .unwrap()
+                .is_equivalent_of(&expected_automaton)
+                .unwrap(),
+            "{regex} {language} {expected}"
+        );
+    }
+}
@@ -0,0 +1,451 @@
+use ahash::AHashMap;
+
+use crate::{
+    error::EngineError,
+    execution_profile::{ExecutionProfile, ThreadLocalParams},
+    transducer::{Output, Transducer},
+};
+
+use super::*;
+
+impl FastAutomaton {
+    /// Compute the automaton matching the strings written by the given transducer when reading a string matched by the current automaton.
+    pub fn image(&self, transducer: &Transducer) -> Result<FastAutomaton, EngineError> {
+        if self.is_empty() {
+            return Ok(Self::new_empty());
+        }
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+        let transitions = self.get_range_transitions()?;
+
+        let mut product = ProductAutomaton::new((transducer.get_start_state(), self.start_state));
+        while let Some((pair, product_state)) = product.next_pair() {
+            execution_profile.assert_not_timed_out()?;
+            execution_profile
+                .assert_max_number_of_states(product.automaton.get_number_of_states())?;
+            let (transducer_state, state) = pair;
+            if transducer.is_accepted(&transducer_state) && self.is_accepted(&state) {
+                product.automaton.accept(product_state);
+            }
+
+            for transition in transducer.transitions_from_state(transducer_state) {
+                let Some(input) = &transition.input else {
+                    let output = Self::get_written_ranges(&transition.output, &Range::empty());
+                    let to_product_state = product.get_state((transition.to_state, state));
+                    product
+                        .automaton
+                        .add_path(product_state, &output, to_product_state);
+                    continue;
+                };
+                for (to_state, range) in &transitions[&state] {
+                    let read = input.intersection(range);
+                    if read.is_empty() {
+                        continue;
+                    }
+                    let output = Self::get_written_ranges(&transition.output, &read);
+                    let to_product_state = product.get_state((transition.to_state, *to_state));
+                    product
+                        .automaton
+                        .add_path(product_state, &output, to_product_state);
+                }
+            }
+        }
+
+        product.automaton.to_automaton(&execution_profile)
+    }
+
+    /// Compute the automaton matching the strings that the given transducer can read while writing a string matched by the current automaton.
+    pub fn pre_image(&self, transducer: &Transducer) -> Result<FastAutomaton, EngineError> {
+        if self.is_empty() {
+            return Ok(Self::new_empty());
+        }
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+        let transitions = self.get_range_transitions()?;
+
+        let mut product = ProductAutomaton::new((transducer.get_start_state(), self.start_state));
+        while let Some((pair, product_state)) = product.next_pair() {
+            execution_profile.assert_not_timed_out()?;
+            execution_profile
+                .assert_max_number_of_states(product.automaton.get_number_of_states())?;
+            let (transducer_state, state) = pair;
+            if transducer.is_accepted(&transducer_state) && self.is_accepted(&state) {
+                product.automaton.accept(product_state);
+            }
+
+            for transition in transducer.transitions_from_state(transducer_state) {
+                // The states reached by writing the output, with the characters that can be read to write it.
+                let mut reached: IntMap<State, Option<Range>> = IntMap::default();
+                reached.insert(state, transition.input.clone());
+                for symbol in &transition.output {
+                    let mut next_reached: IntMap<State, Option<Range>> = IntMap::default();
+                    for (from_state, read) in &reached {
+                        for (to_state, range) in &transitions[from_state] {
+                            let next_read = match symbol {
+                                Output::Input => {
+                                    let read = read.as_ref().map(|read| read.intersection(range));
+                                    match read {
+                                        Some(read) if !read.is_empty() => Some(read),
+                                        _ => continue,
+                                    }
+                                }
+                                Output::Characters(characters) => {
+                                    if !characters.has_intersection(range) {
+                                        continue;
+                                    }
+                                    read.clone()
+                                }
+                            };
+                            match next_reached.entry(*to_state) {
+                                Entry::Occupied(mut o) => {
+                                    let read = o.get().as_ref().zip(next_read.as_ref());
+                                    if let Some((read, next_read)) = read {
+                                        o.insert(Some(read.union(next_read)));
+                                    }
+                                }
+                                Entry::Vacant(v) => {
+                                    v.insert(next_read);
+                                }
+                            }
+                        }
+                    }
+                    reached = next_reached;
+                }
+
+                for (to_state, read) in reached {
+                    let to_product_state = product.get_state((transition.to_state, to_state));
+                    product
+                        .automaton
+                        .add_transition(product_state, read, to_product_state);
+                }
+            }
+        }
+
+        product.automaton.to_automaton(&execution_profile)
+    }
+
+    fn get_range_transitions(&self) -> Result<IntMap<State, Vec<(State, Range)>>, EngineError> {
+        let mut transitions =
+            IntMap::with_capacity_and_hasher(self.get_number_of_states(), Default::default());
+        for from_state in self.transitions_iter() {
+            let mut ranges = vec![];
+            for (to_state, condition) in self.transitions_from_state_enumerate_iter(&from_state) {
+                ranges.push((*to_state, condition.to_range(&self.spanning_set)?));
+            }
+            transitions.insert(from_state, ranges);
+        }
+        Ok(transitions)
+    }
+
+    fn get_written_ranges(output: &[Output], read: &Range) -> Vec<Range> {
+        output
+            .iter()
+            .map(|symbol| match symbol {
+                Output::Input => read.clone(),
+                Output::Characters(characters) => characters.clone(),
+            })
+            .collect()
+    }
+}
+
+/// The product of a transducer and an automaton, explored from its start pair.
+struct ProductAutomaton {
+    automaton: EpsilonAutomaton,
+    states: AHashMap<(usize, State), usize>,
+    worklist: VecDeque<(usize, State)>,
+}
+
+impl ProductAutomaton {
+    fn new(start_pair: (usize, State)) -> Self {
+        ProductAutomaton {
+            automaton: EpsilonAutomaton::new(),
+            states: AHashMap::from([(start_pair, 0)]),
+            worklist: VecDeque::from([start_pair]),
+        }
+    }
+
+    fn next_pair(&mut self) -> Option<((usize, State), usize)> {
+        self.worklist
+            .pop_front()
+            .map(|pair| (pair, self.states[&pair]))
+    }
+
+    fn get_state(&mut self, pair: (usize, State)) -> usize {
+        match self.states.entry(pair) {
+            Entry::Occupied(o) => *o.get(),
+            Entry::Vacant(v) => {
+                self.worklist.push_back(pair);
+                *v.insert(self.automaton.new_state())
+            }
+        }
+    }
+}
+
+/// A nondeterministic automaton with transitions reading nothing, its start state is `0`.
+struct EpsilonAutomaton {
+    transitions: Vec<Vec<(Option<Range>, usize)>>,
+    accept_states: IntSet<usize>,
+}
+
+impl EpsilonAutomaton {
+    fn new() -> Self {
+        EpsilonAutomaton {
+            transitions: vec![vec![]],
+            accept_states: IntSet::default(),
+        }
+    }
+
+    fn new_state(&mut self) -> usize {
+        self.transitions.push(vec![]);
+        self.transitions.len() - 1
+    }
+
+    fn accept(&mut self, state: usize) {
+        self.accept_states.insert(state);
+    }
+
+    #[inline]
+    fn get_number_of_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    fn add_transition(&mut self, from_state: usize, range: Option<Range>, to_state: usize) {
+        if range.as_ref().is_some_and(|range| range.is_empty()) {
+            return;
+        }
+        self.transitions[from_state].push((range, to_state));
+    }
+
+    /// Add a path reading the given ranges one after the other.
+    fn add_path(&mut self, from_state: usize, ranges: &[Range], to_state: usize) {
+        let Some((last_range, ranges)) = ranges.split_last() else {
+            self.add_transition(from_state, None, to_state);
+            return;
+        };
+        let mut state = from_state;
+        for range in ranges {
+            let next_state = self.new_state();
+            self.add_transition(state, Some(range.clone()), next_state);
+            state = next_state;
+        }
+        self.add_transition(state, Some(last_range.clone()), to_state);
+    }
+
+    fn get_epsilon_closure(&self, state: usize) -> IntSet<usize> {
+        let mut closure = IntSet::from_iter([state]);
+        let mut worklist = vec![state];
+        while let Some(state) = worklist.pop() {
+            for (range, to_state) in &self.transitions[state] {
+                if range.is_none() && closure.insert(*to_state) {
+                    worklist.push(*to_state);
+                }
+            }
+        }
+        closure
+    }
+
+    fn to_automaton(
+        &self,
+        execution_profile: &ExecutionProfile,
+    ) -> Result<FastAutomaton, EngineError> {
+        let ranges: Vec<Range> = self
+            .transitions
+            .iter()
+            .flatten()
+            .filter_map(|(range, _)| range.clone())
+            .collect();
+
+        let mut automaton = FastAutomaton::new_empty();
+        automaton.spanning_set = SpanningSet::compute_spanning_set(&ranges);
+        let states: Vec<State> = (0..self.transitions.len())
+            .map(|state| {
+                if state == 0 {
+                    automaton.start_state
+                } else {
+                    automaton.new_state()
+                }
+            })
+            .collect();
+
+        for (from_state, new_from_state) in states.iter().enumerate() {
+            execution_profile.assert_not_timed_out()?;
+            for state in self.get_epsilon_closure(from_state) {
+                if self.accept_states.contains(&state) {
+                    automaton.accept(*new_from_state);
+                }
+                for (range, to_state) in &self.transitions[state] {
+                    if let Some(range) = range {
+                        automaton.add_transition_to(
+                            *new_from_state,
+                            states[*to_state],
+                            &Condition::from_range(range, &automaton.spanning_set)?,
+                        );
+                    }
+                }
+            }
+        }
+
+        automaton.remove_dead_transitions();
+        // The output paths make the product large and highly nondeterministic, it is determinized to keep it small.
+        let mut automaton = automaton.determinize()?;
+        automaton.cyclic = automaton.topological_sorted_states().is_none();
+        Ok(automaton)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::EngineError,
+        execution_profile::{ExecutionProfile, ThreadLocalParams},
+        fast_automaton::FastAutomaton,
+        regex::RegularExpression,
+        transducer::Transducer,
+    };
+
+    #[test]
+    fn test_image() -> Result<(), String> {
+        let remove_script = Transducer::replace_all("<script>", "");
+        assert_transduce("a<script>b|c", FastAutomaton::image, &remove_script, "ab|c");
+        assert_transduce(
+            "<scr<script>ipt>",
+            FastAutomaton::image,
+            &remove_script,
+            "<script>",
+        );
+        assert_transduce("x(<script>)*y", FastAutomaton::image, &remove_script, "xy");
+
+        let replace = Transducer::replace_all("aab", "X");
+        assert_transduce("aab|aaab|aaba", FastAutomaton::image, &replace, "X|aX|Xa");
+        assert_transduce("(aab)*", FastAutomaton::image, &replace, "X*");
+        assert_transduce("a{0,3}", FastAutomaton::image, &replace, "a{0,3}");
+        assert_transduce(
+            "ab",
+            FastAutomaton::image,
+            &Transducer::replace_all("", "-"),
+            "-a-b-",
+        );
+
+        assert_transduce(
+            "<[ab]>'",
+            FastAutomaton::image,
+            &Transducer::html_escape(),
+            "&lt;[ab]&gt;&#x27;",
+        );
+        assert_transduce(
+            "a b/[~_]",
+            FastAutomaton::image,
+            &Transducer::url_encode(),
+            "a%20b%2F[~_]",
+        );
+        assert_transduce(
+            "é",
+            FastAutomaton::image,
+            &Transducer::url_encode(),
+            "%[CD][0-9A-F]%[89AB][0-9A-F]",
+        );
+        assert_transduce(
+            " *a( b)* *",
+            FastAutomaton::image,
+            &Transducer::trim(),
+            "a( b)*",
+        );
+        assert_transduce("[ \t]*", FastAutomaton::image, &Transducer::trim(), "");
+        assert_transduce("[]", FastAutomaton::image, &Transducer::trim(), "[]");
+        Ok(())
+    }
+
+    #[test]
+    fn test_pre_image() -> Result<(), String> {
+        let remove_script = Transducer::replace_all("<script>", "");
+        let automaton = RegularExpression::new(".*<script>.*")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let pre_image = automaton.pre_image(&remove_script).unwrap();
+        assert!(pre_image.match_string("<scr<script>ipt>"));
+        assert!(!pre_image.match_string("<script>"));
+        assert!(!pre_image.match_string("<scr"));
+
+        assert_transduce(
+            ".*<.*",
+            FastAutomaton::pre_image,
+            &Transducer::html_escape(),
+            "[]",
+        );
+        assert_transduce(
+            "&lt;|&amp;",
+            FastAutomaton::pre_image,
+            &Transducer::html_escape(),
+            "[<&]",
+        );
+        assert_transduce(
+            "%2F",
+            FastAutomaton::pre_image,
+            &Transducer::url_encode(),
+            "/",
+        );
+        assert_transduce(
+            "a b",
+            FastAutomaton::pre_image,
+            &Transducer::trim(),
+            "\\s*a b\\s*",
+        );
+        assert_transduce(
+            "X|aX",
+            FastAutomaton::pre_image,
+            &Transducer::replace_all("aab", "X"),
+            "X|aab|aX|aaab",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_transduce_too_many_states() -> Result<(), String> {
+        let automaton = RegularExpression::new("[<>&]{40}")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let escaped_automaton = RegularExpression::new("(&amp;){120}")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let html_escape = Transducer::html_escape();
+
+        ThreadLocalParams::init_profile(&ExecutionProfile {
+            max_number_of_states: 100,
+            start_execution_time: None,
+            execution_timeout: 1500,
+            max_number_of_terms: 50,
+        });
+        assert_eq!(
+            EngineError::AutomatonHasTooManyStates,
+            automaton.image(&html_escape).unwrap_err()
+        );
+        assert_eq!(
+            EngineError::AutomatonHasTooManyStates,
+            escaped_automaton.pre_image(&html_escape).unwrap_err()
+        );
+        Ok(())
+    }
+
+    fn assert_transduce(
+        regex: &str,
+        operation: fn(&FastAutomaton, &Transducer) -> Result<FastAutomaton, EngineError>,
+        transducer: &Transducer,
+        expected: &str,
+    ) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let expected_automaton = RegularExpression::new(expected)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        let result = operation(&automaton, transducer).unwrap();
+        assert!(
+            result.is_equivalent_of(&expected_automaton).unwrap(),
+            "{regex} {expected}"
+        );
+    }
+}
@@ -45,7 +45,7 @@ impl SpanningSet {
         }
     }
 
-    pub fn get_spanning_ranges(&self) -> Iter<RangeSet<Char>> {
+    pub fn get_spanning_ranges(&self) -> Iter<'_, RangeSet<Char>> {
         self.0.iter()
     }
 
@@ -0,0 +1,342 @@
+use serde_json::{json, Map, Value};
+
+use crate::{
+    error::EngineError,
+    regex::{Dialect, RegularExpression},
+    Range, Term,
+};
+
+const DIGITS: &str = "[0-9]+";
+
+impl Term {
+    /// Create a term matching the strings accepted by the given JSON schema.
+    ///
+    /// The keywords `type`, `pattern`, `minLength`, `maxLength`, `const`, `enum` and `format` are combined, the other keywords are ignored. The `pattern` is an ECMAScript regular expression with the `u` flag, matching any string containing a match. The supported formats are `date`, `time`, `date-time`, `duration`, `ipv4` and `uuid`, as defined by RFC 3339 and RFC 4122; in a `date` the 29th of February is accepted whatever the year.
+    ///
+    /// Returns [`EngineError::InvalidJsonSchema`] if a keyword has an invalid value or if the format is not supported.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    /// use serde_json::json;
+    ///
+    /// let old_schema = json!({"type": "string", "pattern": "^[a-z]+$", "maxLength": 8});
+    /// let new_schema = json!({"type": "string", "pattern": "^[a-z0-9]*$"});
+    ///
+    /// let old_term = Term::from_json_schema_string(&old_schema).unwrap();
+    /// let new_term = Term::from_json_schema_string(&new_schema).unwrap();
+    /// assert!(old_term.is_subset_of(&new_term).unwrap());
+    /// ```
+    pub fn from_json_schema_string(schema: &Value) -> Result<Term, EngineError> {
+        let schema = match schema {
+            Value::Bool(true) => {
+                return Ok(Term::RegularExpression(RegularExpression::new_total()))
+            }
+            Value::Bool(false) => {
+                return Ok(Term::RegularExpression(RegularExpression::new_empty()))
+            }
+            Value::Object(schema) => schema,
+            _ => {
+                return Err(Self::invalid_json_schema(
+                    "The schema must be an object or a boolean",
+                ))
+            }
+        };
+
+        let accepts_strings = match schema.get("type") {
+            None => true,
+            Some(Value::String(name)) => name == "string",
+            Some(Value::Array(names)) => names.iter().any(|name| name == "string"),
+            Some(_) => {
+                return Err(Self::invalid_json_schema(
+                    "The keyword `type` must be a string or an array",
+                ))
+            }
+        };
+        if !accepts_strings {
+            return Ok(Term::RegularExpression(RegularExpression::new_empty()));
+        }
+
+        let mut constraints = vec![];
+        if let Some(pattern) = schema.get("pattern") {
+            let Value::String(pattern) = pattern else {
+                return Err(Self::invalid_json_schema(
+                    "The keyword `pattern` must be a string",
+                ));
+            };
+            // The pattern is parsed alone first, so that it can not be unbalanced by the wrapping.
+            RegularExpression::new_with_dialect(pattern, Dialect::EcmaScriptUnicode)?;
+            constraints.push(RegularExpression::new_with_dialect(
+                &format!("[\\s\\S]*(?:{pattern})[\\s\\S]*"),
+                Dialect::EcmaScriptUnicode,
+            )?);
+        }
+
+        let min_length = Self::get_json_schema_length(schema, "minLength")?;
+        let max_length = Self::get_json_schema_length(schema, "maxLength")?;
+        if min_length.is_some() || max_length.is_some() {
+            let min_length = min_length.unwrap_or(0);
+            if max_length.is_some_and(|max_length| max_length < min_length) {
+                return Ok(Term::RegularExpression(RegularExpression::new_empty()));
+            }
+            constraints
+                .push(RegularExpression::Character(Range::total()).repeat(min_length, max_length));
+        }
+
+        if let Some(value) = schema.get("const") {
+            constraints.push(Self::json_schema_literal(value)?);
+        }
+        if let Some(values) = schema.get("enum") {
+            let Value::Array(values) = values else {
+                return Err(Self::invalid_json_schema(
+                    "The keyword `enum` must be an array",
+                ));
+            };
+            let mut union = RegularExpression::new_empty();
+            for value in values {
+                union = union.union(&Self::json_schema_literal(value)?);
+            }
+            constraints.push(union);
+        }
+
+        if let Some(format) = schema.get("format") {
+            let Value::String(format) = format else {
+                return Err(Self::invalid_json_schema(
+                    "The keyword `format` must be a string",
+                ));
+            };
+            let Some(regex) = Self::get_json_schema_format(format) else {
+                return Err(Self::invalid_json_schema(&format!(
+                    "The format `{format}` is not supported"
+                )));
+            };
+            constraints.push(RegularExpression::new(&regex)?);
+        }
+
+        let mut constraints = constraints.into_iter().map(Term::RegularExpression);
+        match constraints.next() {
+            None => Ok(Term::RegularExpression(RegularExpression::new_total())),
+            Some(first) => {
+                let others: Vec<Term> = constraints.collect();
+                if others.is_empty() {
+                    Ok(first)
+                } else {
+                    first.intersection(&others)
+                }
+            }
+        }
+    }
+
+    /// Return a JSON schema accepting the strings matched by the term, with an anchored `pattern` written as an ECMAScript regular expression with the `u` flag.
+    ///
+    /// Returns an error if the term can not be converted to a regular expression.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    /// use serde_json::json;
+    ///
+    /// let term = Term::from_regex("(ab|c)+").unwrap();
+    ///
+    /// assert_eq!(
+    ///     json!({"type": "string", "pattern": "^(?:(?:c|ab)+)$"}),
+    ///     term.to_json_schema_string().unwrap()
+    /// );
+    /// ```
+    pub fn to_json_schema_string(&self) -> Result<Value, EngineError> {
+        let pattern = self.to_regex_string(Dialect::EcmaScriptUnicode)?;
+        Ok(json!({
+            "type": "string",
+            "pattern": format!("^(?:{pattern})$"),
+        }))
+    }
+
+    fn get_json_schema_length(
+        schema: &Map<String, Value>,
+        keyword: &str,
+    ) -> Result<Option<u32>, EngineError> {
+        match schema.get(keyword) {
+            None => Ok(None),
+            Some(value) => match value.as_u64().and_then(|length| u32::try_from(length).ok()) {
+                Some(length) => Ok(Some(length)),
+                None => Err(Self::invalid_json_schema(&format!(
+                    "The keyword `{keyword}` must be a non-negative integer"
+                ))),
+            },
+        }
+    }
+
+    /// Return the regular expression matching the given value, the empty language if it is not a string.
+    fn json_schema_literal(value: &Value) -> Result<RegularExpression, EngineError> {
+        match value {
+            Value::String(string) => RegularExpression::new(&regex_syntax::escape(string)),
+            _ => Ok(RegularExpression::new_empty()),
+        }
+    }
+
+    fn get_json_schema_format(format: &str) -> Option<String> {
+        let date = "[0-9]{4}-(?:(?:0[13578]|1[02])-(?:0[1-9]|[12][0-9]|3[01])|(?:0[469]|11)-(?:0[1-9]|[12][0-9]|30)|02-(?:0[1-9]|[12][0-9]))";
+        let hour = "(?:[01][0-9]|2[0-3])";
+        let time = format!(
+            "{hour}:[0-5][0-9]:(?:[0-5][0-9]|60)(?:\\.[0-9]+)?(?:[Zz]|[+\\-]{hour}:[0-5][0-9])"
+        );
+        let octet = "(?:25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])";
+        let hexadecimal = "[0-9a-fA-F]";
+        match format {
+            "date" => Some(date.to_string()),
+            "time" => Some(time),
+            "date-time" => Some(format!("{date}[Tt]{time}")),
+            "duration" => {
+                let duration_time = format!(
+                    "T(?:{DIGITS}H(?:{DIGITS}M(?:{DIGITS}S)?)?|{DIGITS}M(?:{DIGITS}S)?|{DIGITS}S)"
+                );
+                let duration_date = format!(
+                    "(?:{DIGITS}D|{DIGITS}M(?:{DIGITS}D)?|{DIGITS}Y(?:{DIGITS}M(?:{DIGITS}D)?)?)"
+                );
+                Some(format!(
+                    "P(?:{duration_date}(?:{duration_time})?|{duration_time}|{DIGITS}W)"
+                ))
+            }
+            "ipv4" => Some(format!("{octet}(?:\\.{octet}){{3}}")),
+            "uuid" => Some(format!(
+                "{hexadecimal}{{8}}-{hexadecimal}{{4}}-{hexadecimal}{{4}}-{hexadecimal}{{4}}-{hexadecimal}{{12}}"
+            )),
+            _ => None,
+        }
+    }
+
+    fn invalid_json_schema(message: &str) -> EngineError {
+        EngineError::InvalidJsonSchema(message.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{error::EngineError, Term};
+
+    #[test]
+    fn test_from_json_schema_string() -> Result<(), String> {
+        assert_json_schema(json!({"type": "string"}), ".*");
+        assert_json_schema(
+            json!({"type": ["integer", "string"], "pattern": "b"}),
+            ".*b.*",
+        );
+        assert_json_schema(json!({"type": "integer"}), "[]");
+        assert_json_schema(json!(false), "[]");
+        assert_json_schema(json!({"pattern": "^a|b$"}), "a.*|.*b");
+        assert_json_schema(
+            json!({"pattern": "\\d", "minLength": 2, "maxLength": 3}),
+            "[0-9].{1,2}|.[0-9].?|.{2}[0-9]",
+        );
+        assert_json_schema(json!({"minLength": 3, "maxLength": 2}), "[]");
+        assert_json_schema(json!({"const": "a.b"}), "a\\.b");
+        assert_json_schema(json!({"const": 1}), "[]");
+        assert_json_schema(json!({"enum": ["ab", "c", 1, null], "maxLength": 1}), "c");
+        assert_json_schema(
+            json!({"format": "uuid", "pattern": "^0"}),
+            "0[0-9a-fA-F]{7}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}",
+        );
+
+        assert_json_schema_error(
+            json!({"format": "email"}),
+            "The format `email` is not supported",
+        );
+        assert_json_schema_error(
+            json!({"minLength": -1}),
+            "The keyword `minLength` must be a non-negative integer",
+        );
+        assert_json_schema_error(json!({"enum": "a"}), "The keyword `enum` must be an array");
+        assert_json_schema_error(json!("string"), "The schema must be an object or a boolean");
+        assert!(Term::from_json_schema_string(&json!({"pattern": "(a)\\1"})).is_err());
+        assert!(Term::from_json_schema_string(&json!({"pattern": "a)|(b"})).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_schema_pattern_line_terminators() -> Result<(), String> {
+        let term = Term::from_json_schema_string(&json!({"pattern": "^a.b$"})).unwrap();
+        assert!(term.match_string("axb"));
+        assert!(!term.match_string("a\nb"));
+        assert!(!term.match_string("a\u{2028}b"));
+
+        let term = Term::from_json_schema_string(&json!({"pattern": "a(?s:.)b"})).unwrap();
+        assert!(term.match_string("\na\nb\n"));
+        Ok(())
+    }
+
+    fn assert_json_schema(schema: serde_json::Value, expected: &str) {
+        let term = Term::from_json_schema_string(&schema).unwrap();
+        assert!(
+            term.are_equivalent(&Term::from_regex(expected).unwrap())
+                .unwrap(),
+            "{schema} {term:?}"
+        );
+    }
+
+    fn assert_json_schema_error(schema: serde_json::Value, expected: &str) {
+        assert_eq!(
+            EngineError::InvalidJsonSchema(expected.to_string()),
+            Term::from_json_schema_string(&schema).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_json_schema_formats() -> Result<(), String> {
+        for (format, valid, invalid) in [
+            (
+                "date",
+                vec!["2024-02-29", "1999-12-31"],
+                vec!["2024-02-30", "2024-04-31", "2024-1-01"],
+            ),
+            (
+                "time",
+                vec!["23:59:60Z", "08:30:00.5+02:00"],
+                vec!["24:00:00Z", "08:30:00"],
+            ),
+            (
+                "date-time",
+                vec!["2024-06-01t12:00:00z"],
+                vec!["2024-06-01 12:00:00Z"],
+            ),
+            (
+                "duration",
+                vec!["P1Y2M3DT4H5M6S", "PT1M", "P2W", "P1M"],
+                vec!["P", "PT", "P1H", "P1W2D"],
+            ),
+            (
+                "ipv4",
+                vec!["192.168.0.255", "0.0.0.0"],
+                vec!["256.0.0.1", "01.2.3.4", "1.2.3"],
+            ),
+            (
+                "uuid",
+                vec!["123e4567-e89b-12d3-a456-426614174000"],
+                vec!["123e4567e89b12d3a456426614174000"],
+            ),
+        ] {
+            let term = Term::from_json_schema_string(&json!({ "format": format })).unwrap();
+            for string in valid {
+                assert!(term.match_string(string), "{format} {string}");
+            }
+            for string in invalid {
+                assert!(!term.match_string(string), "{format} {string}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_json_schema_string() -> Result<(), String> {
+        for regex in ["(ab|c)*d{2,5}", "[^a-z]+\\.(x|\\\\)?", "😀.é", "", "[]"] {
+            let term = Term::from_regex(regex).unwrap();
+            let schema = term.to_json_schema_string().unwrap();
+            let parsed = Term::from_json_schema_string(&schema).unwrap();
+            assert!(term.are_equivalent(&parsed).unwrap(), "{regex} {schema}");
+        }
+        Ok(())
+    }
+}
@@ -4,22 +4,26 @@ use std::{
     hash::BuildHasherDefault,
 };
 
-use cardinality::Cardinality;
+use cardinality::{BigUint, Cardinality};
 use error::EngineError;
 use execution_profile::ThreadLocalParams;
-use fast_automaton::FastAutomaton;
+use fast_automaton::{FastAutomaton, StringIterator};
 use nohash_hasher::NoHashHasher;
-use regex::RegularExpression;
+use regex::{Dialect, GlobOptions, RegularExpression};
 use regex_charclass::{char::Char, irange::RangeSet};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use transducer::Transducer;
 
 pub mod cardinality;
 pub mod error;
 pub mod execution_profile;
 pub mod fast_automaton;
+#[cfg(feature = "json-schema")]
+mod json_schema;
 pub mod regex;
 pub mod tokenizer;
+pub mod transducer;
 
 type IntMap<Key, Value> = HashMap<Key, Value, BuildHasherDefault<NoHashHasher<Key>>>;
 type IntSet<Key> = HashSet<Key, BuildHasherDefault<NoHashHasher<Key>>>;
@@ -52,6 +56,101 @@ impl Term {
         Ok(Term::RegularExpression(RegularExpression::new(regex)?))
     }
 
+    /// Create a term based on the given pattern parsed in byte mode, with the semantics of `regex::bytes`.
+    ///
+    /// The byte `b` is represented by the character `U+00b`, use [`Term::generate_bytes`] to get the matched byte strings.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex_bytes("(?-u)\\x89PNG[\\x00-\\xff]{2}").unwrap();
+    /// ```
+    pub fn from_regex_bytes(regex: &str) -> Result<Self, EngineError> {
+        Ok(Term::RegularExpression(RegularExpression::new_bytes(
+            regex,
+        )?))
+    }
+
+    /// Create a term based on the given pattern written in the syntax of the given dialect.
+    ///
+    /// Returns an error if the pattern uses a construct that does not describe a regular language, like a backreference, or that is not supported, like a lookaround.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::{regex::Dialect, Term};
+    ///
+    /// let term = Term::from_regex_with("[[:alpha:]]\\h\\x{41}\\cJ", Dialect::Pcre).unwrap();
+    /// assert_eq!(Term::from_regex("[a-zA-Z][\\t \\xA0\\u{1680}\\u{180E}\\u{2000}-\\u{200A}\\u{202F}\\u{205F}\\u{3000}]A\\n").unwrap(), term);
+    ///
+    /// assert!(Term::from_regex_with("(a+)b\\1", Dialect::EcmaScript).is_err());
+    /// ```
+    pub fn from_regex_with(regex: &str, dialect: Dialect) -> Result<Self, EngineError> {
+        Ok(Term::RegularExpression(
+            RegularExpression::new_with_dialect(regex, dialect)?,
+        ))
+    }
+
+    /// Create a term based on the given glob pattern, with the given options.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::{regex::GlobOptions, Term};
+    ///
+    /// let sources = Term::from_glob("src/**/*.{rs,toml}", GlobOptions::default()).unwrap();
+    /// assert!(Term::from_glob("src/*.rs", GlobOptions::default()).unwrap().is_subset_of(&sources).unwrap());
+    ///
+    /// let bucket = Term::from_glob("arn:aws:s3:::bucket/*", GlobOptions::wildcard()).unwrap();
+    /// let logs = Term::from_glob("arn:aws:s3:::bucket/logs/*", GlobOptions::wildcard()).unwrap();
+    /// assert!(logs.is_subset_of(&bucket).unwrap());
+    /// ```
+    pub fn from_glob(pattern: &str, options: GlobOptions) -> Result<Self, EngineError> {
+        Ok(Term::RegularExpression(RegularExpression::new_glob(
+            pattern, options,
+        )?))
+    }
+
+    /// Create a term based on the given SQL `LIKE` pattern, where `%` matches any sequence of characters and `_` any character, and the character following `escape_char` is literal.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let reports = Term::from_sql_like("report\\_%.pdf", Some('\\')).unwrap();
+    /// let yearly_reports = Term::from_sql_like("report\\_20__.pdf", Some('\\')).unwrap();
+    /// assert!(yearly_reports.is_subset_of(&reports).unwrap());
+    ///
+    /// let drafts = Term::from_sql_like("draft%", None).unwrap();
+    /// assert!(reports.intersection(&[drafts]).unwrap().get_details().unwrap().is_empty());
+    /// ```
+    pub fn from_sql_like(pattern: &str, escape_char: Option<char>) -> Result<Self, EngineError> {
+        Ok(Term::RegularExpression(RegularExpression::new_sql_like(
+            pattern,
+            escape_char,
+        )?))
+    }
+
+    /// Create a term based on the given SQL `SIMILAR TO` pattern, with `\` as escape character.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_sql_similar_to("%(b|d)%").unwrap();
+    /// assert!(term.match_string("abc"));
+    /// assert!(!term.match_string("ace"));
+    /// ```
+    pub fn from_sql_similar_to(pattern: &str) -> Result<Self, EngineError> {
+        Ok(Term::RegularExpression(
+            RegularExpression::new_sql_similar_to(pattern)?,
+        ))
+    }
+
     /// Compute the union of the given collection of terms.
     /// Returns the resulting term.
     ///
@@ -107,11 +206,7 @@ impl Term {
                 return_automaton = return_automaton.union(&return_regex.to_automaton()?)?;
             }
 
-            if let Some(regex) = return_automaton.to_regex() {
-                Ok(Term::RegularExpression(regex))
-            } else {
-                Ok(Term::Automaton(return_automaton))
-            }
+            Self::from_automaton_result(return_automaton)
         }
     }
 
@@ -144,11 +239,7 @@ impl Term {
             }
         }
 
-        if let Some(regex) = return_automaton.to_regex() {
-            Ok(Term::RegularExpression(regex))
-        } else {
-            Ok(Term::Automaton(return_automaton.into_owned()))
-        }
+        Self::from_automaton_result(return_automaton.into_owned())
     }
 
     /// Compute the subtraction/difference of the two given terms.
@@ -175,11 +266,7 @@ impl Term {
             Self::determinize_subtrahend(&minuend_automaton, &subtrahend_automaton)?;
         let return_automaton = minuend_automaton.subtraction(&subtrahend_automaton)?;
 
-        if let Some(regex) = return_automaton.to_regex() {
-            Ok(Term::RegularExpression(regex))
-        } else {
-            Ok(Term::Automaton(return_automaton))
-        }
+        Self::from_automaton_result(return_automaton)
     }
 
     /// See [`Self::subtraction`].
@@ -188,18 +275,481 @@ impl Term {
         self.subtraction(subtrahend)
     }
 
+    /// Compute the concatenation of the given collection of terms, in order.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term1 = Term::from_regex("abc").unwrap();
+    /// let term2 = Term::from_regex("de").unwrap();
+    /// let term3 = Term::from_regex("f*").unwrap();
+    ///
+    /// let concat = term1.concat(&[term2, term3]).unwrap();
+    ///
+    /// if let Term::RegularExpression(regex) = concat {
+    ///     assert_eq!("abcdef*", regex.to_string());
+    /// }
+    /// ```
+    pub fn concat(&self, terms: &[Term]) -> Result<Term, EngineError> {
+        Self::check_number_of_terms(terms)?;
+
+        let operands = std::iter::once(self).chain(terms);
+        if operands
+            .clone()
+            .all(|term| matches!(term, Term::RegularExpression(_)))
+        {
+            let mut return_regex = RegularExpression::new_empty_string();
+            for operand in operands {
+                if let Term::RegularExpression(regex) = operand {
+                    return_regex = return_regex.concat(regex, true);
+                }
+            }
+            return Ok(Term::RegularExpression(return_regex));
+        }
+
+        let mut automatons = Vec::with_capacity(terms.len() + 1);
+        for operand in operands {
+            let automaton = operand.get_automaton()?;
+            if automaton.is_empty() {
+                return Ok(Term::RegularExpression(RegularExpression::new_empty()));
+            }
+            automatons.push(automaton.into_owned());
+        }
+
+        Self::from_automaton_result(FastAutomaton::concatenate(automatons)?)
+    }
+
+    /// Compute the repetition of the given term between `min` and `max_opt` times, `None` meaning unbounded.
+    /// Returns the resulting term, the empty language if `max_opt` is lower than `min`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("abc").unwrap();
+    ///
+    /// let repeat = term.repeat(2, None).unwrap();
+    ///
+    /// if let Term::RegularExpression(regex) = repeat {
+    ///     assert_eq!("(abc){2,}", regex.to_string());
+    /// }
+    /// ```
+    pub fn repeat(&self, min: u32, max_opt: Option<u32>) -> Result<Term, EngineError> {
+        if max_opt.is_some_and(|max| max < min) {
+            return Ok(Term::RegularExpression(RegularExpression::new_empty()));
+        }
+
+        let is_empty = match self {
+            Term::RegularExpression(regex) => regex.is_empty(),
+            Term::Automaton(automaton) => automaton.is_empty(),
+        };
+        if is_empty {
+            // Repeating the empty language zero times matches the empty string.
+            return Ok(Term::RegularExpression(if min == 0 {
+                RegularExpression::new_empty_string()
+            } else {
+                RegularExpression::new_empty()
+            }));
+        }
+
+        match self {
+            Term::RegularExpression(regex) => {
+                Ok(Term::RegularExpression(regex.repeat(min, max_opt)))
+            }
+            Term::Automaton(automaton) => {
+                let mut return_automaton = automaton.clone();
+                return_automaton.repeat(min, max_opt)?;
+
+                Self::from_automaton_result(return_automaton)
+            }
+        }
+    }
+
+    /// Compute the complement of the given term, matching every string it does not match.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("a+").unwrap();
+    ///
+    /// let complement = term.complement().unwrap();
+    ///
+    /// assert!(complement.is_subset_of(&Term::from_regex("(|.*[^a].*)").unwrap()).unwrap());
+    /// ```
+    pub fn complement(&self) -> Result<Term, EngineError> {
+        let automaton = self.get_automaton()?;
+        let mut return_automaton = if automaton.is_determinitic() {
+            automaton.into_owned()
+        } else {
+            automaton.determinize()?
+        };
+        return_automaton.complement()?;
+
+        Self::from_automaton_result(return_automaton)
+    }
+
+    /// Compute the term matching the reversed strings of the given term.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("(abc|de)*f").unwrap();
+    ///
+    /// let reverse = term.reverse().unwrap();
+    ///
+    /// assert!(reverse.are_equivalent(&Term::from_regex("f(cba|ed)*").unwrap()).unwrap());
+    /// ```
+    pub fn reverse(&self) -> Result<Term, EngineError> {
+        match self {
+            Term::RegularExpression(regex) => Ok(Term::RegularExpression(regex.reverse())),
+            Term::Automaton(automaton) => Self::from_automaton_result(automaton.reverse()),
+        }
+    }
+
+    /// Compute the term matching all the prefixes of the strings matched by the given term.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("abc").unwrap();
+    ///
+    /// let prefixes = term.prefixes().unwrap();
+    ///
+    /// assert!(prefixes.are_equivalent(&Term::from_regex("(a(bc?)?)?").unwrap()).unwrap());
+    /// ```
+    pub fn prefixes(&self) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.prefixes())
+    }
+
+    /// Compute the term matching all the suffixes of the strings matched by the given term.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("abc").unwrap();
+    ///
+    /// let suffixes = term.suffixes().unwrap();
+    ///
+    /// assert!(suffixes.are_equivalent(&Term::from_regex("((a?b)?c)?").unwrap()).unwrap());
+    /// ```
+    pub fn suffixes(&self) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.suffixes())
+    }
+
+    /// Compute the term matching all the factors, i.e. the substrings, of the strings matched by the given term.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("abc").unwrap();
+    ///
+    /// let factors = term.factors().unwrap();
+    ///
+    /// assert!(factors.are_equivalent(&Term::from_regex("(a|b|c|ab|bc|abc)?").unwrap()).unwrap());
+    /// ```
+    pub fn factors(&self) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.factors())
+    }
+
+    /// Compute the left quotient of the given term by `prefixes`: the strings `w` such that `uw` is matched by the given term for some `u` matched by `prefixes`.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("/api/v[0-9]+/(users|items)/[0-9]+").unwrap();
+    /// let prefixes = Term::from_regex("/api/v[0-9]+/").unwrap();
+    ///
+    /// let left_quotient = term.left_quotient(&prefixes).unwrap();
+    ///
+    /// assert!(left_quotient.are_equivalent(&Term::from_regex("(users|items)/[0-9]+").unwrap()).unwrap());
+    /// ```
+    pub fn left_quotient(&self, prefixes: &Term) -> Result<Term, EngineError> {
+        Self::from_automaton_result(
+            self.get_automaton()?
+                .left_quotient(&*prefixes.get_automaton()?)?,
+        )
+    }
+
+    /// Compute the right quotient of the given term by `suffixes`: the strings `w` such that `wu` is matched by the given term for some `u` matched by `suffixes`.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("[a-z]+\\.(json|yaml)").unwrap();
+    /// let suffixes = Term::from_regex("\\.json").unwrap();
+    ///
+    /// let right_quotient = term.right_quotient(&suffixes).unwrap();
+    ///
+    /// assert!(right_quotient.are_equivalent(&Term::from_regex("[a-z]+").unwrap()).unwrap());
+    /// ```
+    pub fn right_quotient(&self, suffixes: &Term) -> Result<Term, EngineError> {
+        Self::from_automaton_result(
+            self.get_automaton()?
+                .right_quotient(&*suffixes.get_automaton()?)?,
+        )
+    }
+
+    /// Compute the term matching the strings within `k` edits, i.e. insertions, deletions or substitutions of a character, of a string matched by the given term.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("select").unwrap();
+    ///
+    /// let typos = term.within_edit_distance(1).unwrap().subtraction(&term).unwrap();
+    ///
+    /// assert!(Term::from_regex("selct|slect|selectt|sekect").unwrap().is_subset_of(&typos).unwrap());
+    /// ```
+    pub fn within_edit_distance(&self, k: u32) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.within_edit_distance(k)?)
+    }
+
+    /// Compute the term matching the strings within `k` substitutions of a character of a string matched by the given term.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("abc").unwrap();
+    ///
+    /// let neighborhood = term.within_hamming_distance(1).unwrap();
+    ///
+    /// assert!(neighborhood.are_equivalent(&Term::from_regex(".bc|a.c|ab.").unwrap()).unwrap());
+    /// ```
+    pub fn within_hamming_distance(&self, k: u32) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.within_hamming_distance(k)?)
+    }
+
+    /// Compute the term matching the images of the strings matched by the given term through the given character mapping.
+    /// Returns the resulting term.
+    ///
+    /// The mapping is called with disjoint ranges of characters covering the characters used by the term, a character is mapped to the image of the range containing it.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    /// use regex_charclass::{char::Char, irange::RangeSet};
+    ///
+    /// let term = Term::from_regex("[0-9]+").unwrap();
+    ///
+    /// let masked = term.map_chars(|_| RangeSet::new_from_range(Char::new('*')..=Char::new('*'))).unwrap();
+    ///
+    /// assert!(masked.are_equivalent(&Term::from_regex("\\*+").unwrap()).unwrap());
+    /// ```
+    pub fn map_chars(
+        &self,
+        mapping: impl Fn(&RangeSet<Char>) -> RangeSet<Char>,
+    ) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.map_chars(mapping)?)
+    }
+
+    /// Compute the term where each occurrence of a character of `char_class` in the strings matched by the given term is replaced by a string matched by `term`.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    /// use regex_charclass::{char::Char, irange::RangeSet};
+    ///
+    /// let term = Term::from_regex("v[0-9]+\\.[0-9]+").unwrap();
+    /// let dot = RangeSet::new_from_range(Char::new('.')..=Char::new('.'));
+    ///
+    /// let escaped = term.substitute(&dot, &Term::from_regex("\\\\\\.").unwrap()).unwrap();
+    ///
+    /// assert!(escaped.are_equivalent(&Term::from_regex("v[0-9]+\\\\\\.[0-9]+").unwrap()).unwrap());
+    /// ```
+    pub fn substitute(
+        &self,
+        char_class: &RangeSet<Char>,
+        term: &Term,
+    ) -> Result<Term, EngineError> {
+        Self::from_automaton_result(
+            self.get_automaton()?
+                .substitute(char_class, &*term.get_automaton()?)?,
+        )
+    }
+
+    /// Compute the term matching the strings written by the given transducer when reading a string matched by the given term.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::{transducer::Transducer, Term};
+    ///
+    /// let input = Term::from_regex("[a-z<>/]*").unwrap();
+    /// let sanitized = input.image(&Transducer::replace_all("<script>", "")).unwrap();
+    ///
+    /// let forbidden = Term::from_regex(".*<script>.*").unwrap();
+    /// let bypasses = sanitized.intersection(&[forbidden]).unwrap();
+    /// assert!(!bypasses.get_details().unwrap().is_empty());
+    /// ```
+    pub fn image(&self, transducer: &Transducer) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.image(transducer)?)
+    }
+
+    /// Compute the term matching the strings that the given transducer can read while writing a string matched by the given term.
+    /// Returns the resulting term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::{transducer::Transducer, Term};
+    ///
+    /// let term = Term::from_regex(".*<.*").unwrap();
+    ///
+    /// let pre_image = term.pre_image(&Transducer::html_escape()).unwrap();
+    ///
+    /// assert!(pre_image.get_details().unwrap().is_empty());
+    /// ```
+    pub fn pre_image(&self, transducer: &Transducer) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.pre_image(transducer)?)
+    }
+
+    /// Compute the shortest regular expression found matching the same strings as the given term.
+    /// Returns the resulting term, or the given term if no shorter regular expression is found.
+    ///
+    /// The candidates are the regular expression rebuilt with [`RegularExpression::simplify`] and the one converted from the minimal automaton, along with the results of [`RegularExpression::apply_rewrite_laws`] and [`RegularExpression::factor_common_affixes`] on each of them. Every candidate is checked to be equivalent to the given term.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::{regex::RegularExpression, Term};
+    ///
+    /// let regex = RegularExpression::new("(a*|b)*").unwrap();
+    /// let term = Term::RegularExpression(regex);
+    ///
+    /// let simplified = term.simplify().unwrap();
+    ///
+    /// assert_eq!(Term::from_regex("[ab]*").unwrap(), simplified);
+    /// ```
+    pub fn simplify(&self) -> Result<Term, EngineError> {
+        let automaton = self.get_automaton()?;
+        let mut rewritten = vec![];
+        if let Term::RegularExpression(regex) = self {
+            rewritten.push(regex.clone());
+            rewritten.push(regex.simplify());
+        }
+        if let Some(regex) = automaton.minimize()?.to_regex() {
+            rewritten.push(regex);
+        }
+        let mut candidates = Vec::with_capacity(rewritten.len() * 4);
+        for regex in rewritten {
+            let regex_with_laws = regex.apply_rewrite_laws();
+            candidates.push(regex_with_laws.factor_common_affixes());
+            candidates.push(regex_with_laws);
+            candidates.push(regex.factor_common_affixes());
+            candidates.push(regex);
+        }
+
+        let mut simplified = self.clone();
+        let mut length = match self {
+            Term::RegularExpression(regex) => regex.to_string().chars().count(),
+            Term::Automaton(_) => usize::MAX,
+        };
+        for candidate in candidates {
+            let candidate_length = candidate.to_string().chars().count();
+            if candidate_length < length
+                && candidate.to_automaton()?.is_equivalent_of(&automaton)?
+            {
+                length = candidate_length;
+                simplified = Term::RegularExpression(candidate);
+            }
+        }
+        Ok(simplified)
+    }
+
+    /// Return an equivalent term built from the minimal deterministic automaton of the given term.
+    ///
+    /// The results of the operations are not minimized, this can be used to shrink the automata that are stored or used in further operations.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term1 = Term::from_regex("(ab|cd)*").unwrap();
+    /// let term2 = Term::from_regex("(ab)*").unwrap();
+    ///
+    /// let subtraction = term1.subtraction(&term2).unwrap();
+    /// let minimized = subtraction.minimize().unwrap();
+    ///
+    /// assert!(minimized.are_equivalent(&subtraction).unwrap());
+    /// ```
+    pub fn minimize(&self) -> Result<Term, EngineError> {
+        Self::from_automaton_result(self.get_automaton()?.minimize()?)
+    }
+
+    /// Write the given term as a regular expression in the syntax of the given dialect.
+    ///
+    /// Returns an error if the term can not be converted to a regular expression, or if it uses a construct that can not be expressed in the dialect.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::{regex::Dialect, Term};
+    ///
+    /// let term = Term::from_regex("(ab|c)+\\.😀").unwrap();
+    ///
+    /// assert_eq!("(?:c|ab)+\\.\\uD83D\\uDE00", term.to_regex_string(Dialect::EcmaScript).unwrap());
+    /// assert_eq!("(c|ab)+\\.😀", term.to_regex_string(Dialect::PosixEre).unwrap());
+    /// ```
+    pub fn to_regex_string(&self, dialect: Dialect) -> Result<String, EngineError> {
+        match self {
+            Term::RegularExpression(regex) => regex.to_dialect(dialect),
+            Term::Automaton(automaton) => match automaton.to_regex() {
+                Some(regex) => regex.to_dialect(dialect),
+                None => Err(EngineError::RegexCannotBeExpressed(String::from(
+                    "The automaton can not be converted to a regular expression",
+                ))),
+            },
+        }
+    }
+
     /// Returns the Details of the given term.
     ///
     /// # Example:
     ///
     /// ```
-    /// use regexsolver::{Term, cardinality::Cardinality};
+    /// use regexsolver::{Term, cardinality::{BigUint, Cardinality}};
     ///
     /// let term = Term::from_regex("(abc|de)").unwrap();
     ///
     /// let details = term.get_details().unwrap();
     ///
-    /// assert_eq!(Some(Cardinality::Integer(2)), *details.get_cardinality());
+    /// assert_eq!(Some(Cardinality::Integer(BigUint::from(2u32))), *details.get_cardinality());
     /// assert_eq!((Some(2), Some(3)), *details.get_length());
     /// assert!(!details.is_empty());
     /// assert!(!details.is_total());
@@ -207,7 +757,7 @@ impl Term {
     pub fn get_details(&self) -> Result<Details, EngineError> {
         match self {
             Term::RegularExpression(regex) => Ok(Details {
-                cardinality: Some(regex.get_cardinality()),
+                cardinality: Some(regex.get_cardinality()?),
                 length: regex.get_length(),
                 empty: regex.is_empty(),
                 total: regex.is_total(),
@@ -221,6 +771,27 @@ impl Term {
         }
     }
 
+    /// Return the number of unique strings matched by the given term whose length is at most `max_len`.
+    ///
+    /// Unlike [`Details::get_cardinality`], the result is always finite.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::{Term, cardinality::BigUint};
+    ///
+    /// let term = Term::from_regex("(abc|de)*").unwrap();
+    ///
+    /// assert_eq!(BigUint::from(6u32), term.get_cardinality_up_to(5).unwrap()); // "", "de", "abc", "dede", "abcde", "deabc"
+    /// ```
+    pub fn get_cardinality_up_to(&self, max_len: usize) -> Result<BigUint, EngineError> {
+        Ok(self
+            .get_automaton()?
+            .count_by_length(max_len)?
+            .into_iter()
+            .sum())
+    }
+
     /// Generate strings matched by the given term.
     ///
     /// # Example:
@@ -242,6 +813,177 @@ impl Term {
             .collect())
     }
 
+    /// Generate the given count of byte strings matched by the given term, only the characters of the byte alphabet `U+0000-U+00FF` are considered.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex_bytes("(?-u)\\xff(é|\\x00)").unwrap();
+    ///
+    /// let mut bytes = term.generate_bytes(3).unwrap();
+    /// bytes.sort();
+    ///
+    /// assert_eq!(vec![vec![0xff, 0x00], vec![0xff, 0xc3, 0xa9]], bytes);
+    /// ```
+    pub fn generate_bytes(&self, count: usize) -> Result<Vec<Vec<u8>>, EngineError> {
+        let byte_strings = RegularExpression::Character(Range::new_from_range(
+            Char::new('\0')..=Char::new('\u{FF}'),
+        ))
+        .repeat(0, None)
+        .to_automaton()?;
+        Ok(self
+            .get_automaton()?
+            .intersection(&byte_strings)?
+            .generate_strings(count)?
+            .into_iter()
+            .map(|string| string.chars().map(|character| character as u8).collect())
+            .collect())
+    }
+
+    /// Draw `count` strings of length at most `max_len` uniformly at random among the strings matched by the given term.
+    ///
+    /// The random generator is seeded with `rng_seed`, so the same arguments always give the same strings.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("(abc|de)*").unwrap();
+    ///
+    /// let strings = term.sample_strings(3, 10, 42).unwrap();
+    ///
+    /// assert_eq!(3, strings.len()); // ex: ["deabcabc", "dedeabc", "abcdede"]
+    /// ```
+    pub fn sample_strings(
+        &self,
+        count: usize,
+        max_len: usize,
+        rng_seed: u64,
+    ) -> Result<Vec<String>, EngineError> {
+        self.get_automaton()?
+            .sample_strings(count, max_len, rng_seed)
+    }
+
+    /// Return a lazy iterator over the strings matched by the given term in shortlex order: shorter strings first, strings of the same length in ascending order of their characters.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("(abc|de)*").unwrap();
+    ///
+    /// let strings: Vec<String> = term.strings().unwrap().take(4).collect();
+    ///
+    /// assert_eq!(vec!["", "de", "abc", "dede"], strings);
+    /// ```
+    pub fn strings(&self) -> Result<StringIterator, EngineError> {
+        self.get_automaton()?.strings()
+    }
+
+    /// Return a lazy iterator over the strings matched by the given term coming strictly after `last` in shortlex order.
+    ///
+    /// This allows to resume an enumeration started with [`Term::strings`] from the last string received.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("(abc|de)*").unwrap();
+    ///
+    /// let strings: Vec<String> = term.strings_after("abc").unwrap().take(3).collect();
+    ///
+    /// assert_eq!(vec!["dede", "abcde", "deabc"], strings);
+    /// ```
+    pub fn strings_after(&self, last: &str) -> Result<StringIterator, EngineError> {
+        self.get_automaton()?.strings_after(last)
+    }
+
+    /// Compute if the given string is matched by the given term.
+    ///
+    /// A regular expression is matched by taking successive derivatives, without building an automaton.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex(".{0,5000}abc").unwrap();
+    ///
+    /// assert!(term.match_string("xyzabc"));
+    /// assert!(!term.match_string("abcxyz"));
+    /// ```
+    pub fn match_string(&self, input: &str) -> bool {
+        match self {
+            Term::RegularExpression(regex) => regex.match_string(input),
+            Term::Automaton(automaton) => automaton.match_string(input),
+        }
+    }
+
+    /// Return the shortest string matched by the given term, the lowest one in ascending order of characters if there are several, or `None` if the term is empty.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("(abc|de)+").unwrap();
+    ///
+    /// assert_eq!(Some("de".to_string()), term.shortest_string().unwrap());
+    /// ```
+    pub fn shortest_string(&self) -> Result<Option<String>, EngineError> {
+        self.get_automaton()?.shortest_string()
+    }
+
+    /// Return the longest string matched by the given term, the lowest one in ascending order of characters if there are several, or `None` if the term is empty or matches strings of unbounded length.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("(abc|de){1,2}").unwrap();
+    ///
+    /// assert_eq!(Some("abcabc".to_string()), term.longest_string().unwrap());
+    /// ```
+    pub fn longest_string(&self) -> Result<Option<String>, EngineError> {
+        self.get_automaton()?.longest_string()
+    }
+
+    /// Return the lowest string of length `len` matched by the given term in ascending order of characters, or `None` if no string of this length is matched.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("[a-z]+@[a-z]+").unwrap();
+    ///
+    /// assert_eq!(Some("a@a".to_string()), term.lexicographic_min(3).unwrap());
+    /// ```
+    pub fn lexicographic_min(&self, len: usize) -> Result<Option<String>, EngineError> {
+        self.get_automaton()?.lexicographic_min(len)
+    }
+
+    /// Return the greatest string of length `len` matched by the given term in ascending order of characters, or `None` if no string of this length is matched.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term = Term::from_regex("[a-z]+@[a-z]+").unwrap();
+    ///
+    /// assert_eq!(Some("zz@z".to_string()), term.lexicographic_max(4).unwrap());
+    /// ```
+    pub fn lexicographic_max(&self, len: usize) -> Result<Option<String>, EngineError> {
+        self.get_automaton()?.lexicographic_max(len)
+    }
+
     /// Compute if the two given terms are equivalent.
     ///
     /// # Example:
@@ -286,6 +1028,51 @@ impl Term {
         automaton_1.is_subset_of(&automaton_2)
     }
 
+    /// Return the shortest string matched by the first term but not by the second one, or `None` if the first term is a subset of the second one.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term1 = Term::from_regex("(abc|de)").unwrap();
+    /// let term2 = Term::from_regex("de").unwrap();
+    ///
+    /// assert_eq!(Some("abc".to_string()), term1.subset_counterexample(&term2).unwrap());
+    /// assert_eq!(None, term2.subset_counterexample(&term1).unwrap());
+    /// ```
+    pub fn subset_counterexample(&self, that: &Term) -> Result<Option<String>, EngineError> {
+        if self == that {
+            return Ok(None);
+        }
+
+        let automaton_1 = self.get_automaton()?;
+        let automaton_2 = that.get_automaton()?;
+        automaton_1.get_subset_counterexample(&automaton_2)
+    }
+
+    /// Return the shortest string matched by only one of the two given terms, or `None` if they are equivalent.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use regexsolver::Term;
+    ///
+    /// let term1 = Term::from_regex("(abc|de)").unwrap();
+    /// let term2 = Term::from_regex("(abc|de)*").unwrap();
+    ///
+    /// assert_eq!(Some("".to_string()), term1.distinguishing_string(&term2).unwrap());
+    /// ```
+    pub fn distinguishing_string(&self, that: &Term) -> Result<Option<String>, EngineError> {
+        if self == that {
+            return Ok(None);
+        }
+
+        let automaton_1 = self.get_automaton()?;
+        let automaton_2 = that.get_automaton()?;
+        automaton_1.get_distinguishing_string(&automaton_2)
+    }
+
     fn check_number_of_terms(terms: &[Term]) -> Result<(), EngineError> {
         let number_of_terms = terms.len() + 1;
         let max_number_of_terms = ThreadLocalParams::get_max_number_of_terms();
@@ -312,7 +1099,16 @@ impl Term {
         }
     }
 
-    fn get_automaton(&self) -> Result<Cow<FastAutomaton>, EngineError> {
+    /// Convert the automaton resulting from an operation to a term.
+    fn from_automaton_result(automaton: FastAutomaton) -> Result<Term, EngineError> {
+        if let Some(regex) = automaton.to_regex() {
+            Ok(Term::RegularExpression(regex))
+        } else {
+            Ok(Term::Automaton(automaton))
+        }
+    }
+
+    fn get_automaton(&self) -> Result<Cow<'_, FastAutomaton>, EngineError> {
         Ok(match self {
             Term::RegularExpression(regex) => Cow::Owned(regex.to_automaton()?),
             Term::Automaton(automaton) => Cow::Borrowed(automaton),
@@ -325,7 +1121,7 @@ impl Term {
 #[derive(Clone, PartialEq, Eq, Debug)]
 #[cfg_attr(feature = "serde", serde(tag = "type", rename = "details"))]
 pub struct Details {
-    cardinality: Option<Cardinality<u32>>,
+    cardinality: Option<Cardinality<BigUint>>,
     length: (Option<u32>, Option<u32>),
     empty: bool,
     total: bool,
@@ -333,7 +1129,7 @@ pub struct Details {
 
 impl Details {
     /// Return the number of unique strings matched.
-    pub fn get_cardinality(&self) -> &Option<Cardinality<u32>> {
+    pub fn get_cardinality(&self) -> &Option<Cardinality<BigUint>> {
         &self.cardinality
     }
 
@@ -364,7 +1160,7 @@ mod tests {
         let regex1 = Term::from_regex("a").unwrap();
         let regex2 = Term::from_regex("b").unwrap();
 
-        let details = regex1.intersection(&vec![regex2]);
+        let details = regex1.intersection(&[regex2]);
         assert!(details.is_ok());
 
         Ok(())
@@ -407,7 +1203,7 @@ mod tests {
         let regex1 = Term::from_regex("a*").unwrap();
         let regex2 = Term::from_regex("b*").unwrap();
 
-        let result = regex1.intersection(&vec![regex2]);
+        let result = regex1.intersection(&[regex2]);
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(Term::from_regex("").unwrap(), result);
@@ -420,7 +1216,7 @@ mod tests {
         let regex1 = Term::from_regex("x*").unwrap();
         let regex2 = Term::from_regex("(xxx)*").unwrap();
 
-        let result = regex1.intersection(&vec![regex2]);
+        let result = regex1.intersection(&[regex2]);
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(
@@ -431,6 +1227,155 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_subtraction_minimized() -> Result<(), String> {
+        let regex1 = Term::from_regex("(ab|cd)*").unwrap();
+        let regex2 = Term::from_regex("(ab)*").unwrap();
+
+        let subtraction = regex1.subtraction(&regex2).unwrap();
+        let result = subtraction.minimize().unwrap();
+        assert!(result
+            .are_equivalent(&Term::from_regex("(ab)*cd(ab|cd)*").unwrap())
+            .unwrap());
+        assert!(
+            result.get_automaton().unwrap().get_number_of_states()
+                <= subtraction.get_automaton().unwrap().get_number_of_states()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simplify() -> Result<(), String> {
+        // The minimal automata of these terms can not be converted to regular expressions.
+        assert_simplify("a(b*|d*|e*)c|a*[ab]*", "([ab]*|a(b*|d*|e*)c)");
+        assert_simplify("a(b*|d*|e*)c|dog|dot", "(a(b*|d*|e*)c|do[gt])");
+
+        assert_simplify("a*[ab]*x|a(b*|d*|e*)c", "(a(b*|d*|e*)c|[ab]*x)");
+        assert_simplify("(a*|b)*", "[ab]*");
+        Ok(())
+    }
+
+    fn assert_simplify(regex: &str, expected: &str) {
+        let term = Term::from_regex(regex).unwrap();
+        let simplified = term.simplify().unwrap();
+        let Term::RegularExpression(simplified_regex) = &simplified else {
+            panic!("{regex} is not simplified to a regular expression");
+        };
+        assert_eq!(expected, simplified_regex.to_string(), "{regex}");
+        assert!(term.are_equivalent(&simplified).unwrap());
+    }
+
+    #[test]
+    fn test_concat_repeat_complement() -> Result<(), String> {
+        let prefix = Term::from_regex("x").unwrap();
+        let term1 = Term::from_regex("(ab|cd)").unwrap();
+        let term2 = Term::from_regex("cd").unwrap();
+
+        let result = prefix
+            .concat(&[term1.subtraction(&term2).unwrap().repeat(0, None).unwrap()])
+            .unwrap();
+        assert!(result
+            .are_equivalent(&Term::from_regex("x(ab)*").unwrap())
+            .unwrap());
+
+        let automaton = Term::Automaton(
+            RegularExpression::new("a{2}")
+                .unwrap()
+                .to_automaton()
+                .unwrap(),
+        );
+        let result = automaton.concat(&[Term::from_regex("b").unwrap()]).unwrap();
+        assert_eq!(Term::from_regex("a{2}b").unwrap(), result);
+
+        let result = automaton.repeat(1, Some(2)).unwrap();
+        assert!(result
+            .are_equivalent(&Term::from_regex("a{2}|a{4}").unwrap())
+            .unwrap());
+
+        let empty = Term::Automaton(FastAutomaton::new_empty());
+        assert_eq!(
+            Term::from_regex("").unwrap(),
+            empty.repeat(0, None).unwrap()
+        );
+        assert_eq!(
+            Term::from_regex("[]").unwrap(),
+            empty.repeat(1, None).unwrap()
+        );
+        let empty = Term::from_regex("[]").unwrap();
+        assert_eq!(
+            Term::from_regex("").unwrap(),
+            empty.repeat(0, Some(3)).unwrap()
+        );
+        assert_eq!(
+            Term::from_regex("[]").unwrap(),
+            empty.repeat(2, Some(3)).unwrap()
+        );
+
+        for term in [
+            Term::from_regex("ab").unwrap(),
+            Term::Automaton(
+                RegularExpression::new("ab")
+                    .unwrap()
+                    .to_automaton()
+                    .unwrap(),
+            ),
+        ] {
+            assert_eq!(
+                Term::from_regex("[]").unwrap(),
+                term.repeat(3, Some(1)).unwrap()
+            );
+        }
+
+        let result = Term::from_regex("a*").unwrap().complement().unwrap();
+        assert!(result
+            .are_equivalent(&Term::from_regex("a*[^a].*").unwrap())
+            .unwrap());
+        assert!(result
+            .complement()
+            .unwrap()
+            .are_equivalent(&Term::from_regex("a*").unwrap())
+            .unwrap());
+
+        let result = Term::from_regex("[]").unwrap().complement().unwrap();
+        assert!(result.get_details().unwrap().is_total());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inline_flags() -> Result<(), String> {
+        let term1 = Term::from_regex("(?i)abc").unwrap();
+        let term2 = Term::from_regex("abc").unwrap();
+
+        assert!(term2.is_subset_of(&term1).unwrap());
+        assert!(!term1.is_subset_of(&term2).unwrap());
+        assert!(term1
+            .are_equivalent(&Term::from_regex("[aA][bB][cC]").unwrap())
+            .unwrap());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_details_serialization() -> Result<(), String> {
+        let details = Term::from_regex("[a-z]{20}")
+            .unwrap()
+            .get_details()
+            .unwrap();
+        assert_eq!(
+            &Some(Cardinality::Integer(BigUint::from(26u32).pow(20))),
+            details.get_cardinality()
+        );
+
+        let serialized = serde_json::to_string(&details).unwrap();
+        let unserialized: Details = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(details, unserialized);
+
+        Ok(())
+    }
+
     #[test]
     fn test__() -> Result<(), String> {
         let term = Term::from_regex("(abc|de){2}").unwrap();
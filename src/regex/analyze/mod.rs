@@ -1,10 +1,14 @@
-use self::cardinality::Cardinality;
+use self::cardinality::{BigUint, Cardinality};
+use execution_profile::ExecutionProfile;
 
 use super::*;
 
 mod affixes;
 mod number_of_states;
 
+/// The number of bits of the number of characters.
+const CHARACTER_BITS: u64 = (u32::BITS - (char::MAX as u32).leading_zeros()) as u64;
+
 impl RegularExpression {
     pub fn get_length(&self) -> (Option<u32>, Option<u32>) {
         match self {
@@ -84,31 +88,35 @@ impl RegularExpression {
         }
     }
 
-    pub fn get_cardinality(&self) -> Cardinality<u32> {
+    pub fn get_cardinality(&self) -> Result<Cardinality<BigUint>, EngineError> {
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+        self.compute_cardinality(&execution_profile)
+    }
+
+    fn compute_cardinality(
+        &self,
+        execution_profile: &ExecutionProfile,
+    ) -> Result<Cardinality<BigUint>, EngineError> {
         if self.is_empty() {
-            return Cardinality::Integer(0);
+            return Ok(Cardinality::Integer(BigUint::ZERO));
         } else if self.is_total() {
-            return Cardinality::Infinite;
+            return Ok(Cardinality::Infinite);
         }
-        match self {
-            RegularExpression::Character(range) => Cardinality::Integer(range.get_cardinality()),
+        Ok(match self {
+            RegularExpression::Character(range) => {
+                Cardinality::Integer(BigUint::from(range.get_cardinality()))
+            }
             RegularExpression::Repetition(regular_expression, min, max_opt) => {
                 if let Some(max) = max_opt {
-                    let regex_cardinality = regular_expression.get_cardinality();
+                    let regex_cardinality =
+                        regular_expression.compute_cardinality(execution_profile)?;
                     if let Cardinality::Integer(cardinality) = regex_cardinality {
-                        let mut cardinality_temp: u32 = 0;
-                        for i in *min..*max + 1 {
-                            if let Some(pow) = cardinality.checked_pow(i) {
-                                if let Some(add) = cardinality_temp.checked_add(pow) {
-                                    cardinality_temp = add;
-                                } else {
-                                    return Cardinality::BigInteger;
-                                }
-                            } else {
-                                return Cardinality::BigInteger;
-                            }
-                        }
-                        Cardinality::Integer(cardinality_temp)
+                        Cardinality::Integer(Self::sum_of_powers(
+                            &cardinality,
+                            *min,
+                            *max,
+                            execution_profile,
+                        )?)
                     } else {
                         regex_cardinality
                     }
@@ -117,43 +125,67 @@ impl RegularExpression {
                 }
             }
             RegularExpression::Concat(concat) => {
-                let mut cardinality: u32 = 1;
+                let mut cardinality = BigUint::from(1u32);
                 for concat_element in concat {
-                    let element_cardinality = concat_element.get_cardinality();
+                    let element_cardinality =
+                        concat_element.compute_cardinality(execution_profile)?;
                     if let Cardinality::Integer(element_cardinality) = element_cardinality {
-                        if let Some(mult) = cardinality.checked_mul(element_cardinality) {
-                            cardinality = mult;
-                        } else {
-                            return Cardinality::BigInteger;
-                        }
+                        cardinality *= element_cardinality;
                     } else {
-                        return element_cardinality;
+                        return Ok(element_cardinality);
                     }
                 }
                 Cardinality::Integer(cardinality)
             }
             RegularExpression::Alternation(alternation) => {
-                let mut cardinality: u32 = 0;
+                let mut cardinality = BigUint::ZERO;
                 for alternation_element in alternation {
-                    let element_cardinality = alternation_element.get_cardinality();
+                    let element_cardinality =
+                        alternation_element.compute_cardinality(execution_profile)?;
                     if let Cardinality::Integer(element_cardinality) = element_cardinality {
-                        if let Some(add) = cardinality.checked_add(element_cardinality) {
-                            cardinality = add;
-                        } else {
-                            return Cardinality::BigInteger;
-                        }
+                        cardinality += element_cardinality;
                     } else {
-                        return element_cardinality;
+                        return Ok(element_cardinality);
                     }
                 }
                 Cardinality::Integer(cardinality)
             }
+        })
+    }
+
+    /// Compute the sum of `base^i` for `i` in `min..=max`.
+    ///
+    /// Returns [`EngineError::AutomatonHasTooManyStates`] if the sum is larger than the cardinality of any automaton with less than `max_number_of_states` states, which bounds the size of the computed integers.
+    fn sum_of_powers(
+        base: &BigUint,
+        min: u32,
+        max: u32,
+        execution_profile: &ExecutionProfile,
+    ) -> Result<BigUint, EngineError> {
+        if min > max {
+            return Ok(BigUint::ZERO);
+        } else if *base == BigUint::ZERO {
+            return Ok(BigUint::from(u32::from(min == 0)));
+        } else if *base == BigUint::from(1u32) {
+            return Ok(BigUint::from(max - min) + 1u32);
         }
+
+        // An automaton with `n` states matches at most `CHARACTERS^n` strings.
+        let max_number_of_bits =
+            (execution_profile.max_number_of_states as u64).saturating_mul(CHARACTER_BITS);
+        if u64::from(max).saturating_mul(base.bits()) > max_number_of_bits {
+            return Err(EngineError::AutomatonHasTooManyStates);
+        }
+        execution_profile.assert_not_timed_out()?;
+
+        Ok((base.pow(max) * base - base.pow(min)) / (base - 1u32))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::{Duration, SystemTime};
+
     use super::*;
 
     #[test]
@@ -213,6 +245,55 @@ mod tests {
         assert_cardinality("(t){1,3}");
         assert_cardinality("(ot?d){1,4}");
         assert_cardinality("((aad|ads|a)*abc.*def.*uif(aad|ads|x)*abc.*oxs.*def(aad|ads|ax)*abc.*def.*ksd|q){1,100}");
+        assert_cardinality("[a-z]{10}");
+        assert_cardinality("(x|[a-z]{3}y){2,10}");
+
+        assert_eq!(
+            Cardinality::Integer(BigUint::from(26u32).pow(10)),
+            RegularExpression::new("[a-z]{10}")
+                .unwrap()
+                .get_cardinality()
+                .unwrap()
+        );
+        assert_eq!(
+            Cardinality::Integer(BigUint::from(u32::MAX - 1)),
+            RegularExpression::new("(ab){2,4294967295}")
+                .unwrap()
+                .get_cardinality()
+                .unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cardinality_timeout() -> Result<(), String> {
+        let regex = RegularExpression::new(".{0,1000}").unwrap();
+
+        ThreadLocalParams::init_profile(&ExecutionProfile {
+            max_number_of_states: 8192,
+            start_execution_time: Some(SystemTime::now() - Duration::from_secs(1)),
+            execution_timeout: 100,
+            max_number_of_terms: 50,
+        });
+        assert_eq!(
+            EngineError::OperationTimeOutError,
+            regex.get_cardinality().unwrap_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cardinality_too_many_states() -> Result<(), String> {
+        for regex in [".{0,30000}", "[ab]{0,4294967295}", "(.{0,8000}){0,8000}"] {
+            assert_eq!(
+                EngineError::AutomatonHasTooManyStates,
+                RegularExpression::new(regex)
+                    .unwrap()
+                    .get_cardinality()
+                    .unwrap_err(),
+                "{regex}"
+            );
+        }
         Ok(())
     }
 
@@ -220,7 +301,7 @@ mod tests {
         println!("{}", regex);
         let regex = RegularExpression::new(regex).unwrap();
 
-        let cardinality = regex.get_cardinality();
+        let cardinality = regex.get_cardinality().unwrap();
 
         let mut automaton = regex.to_automaton().unwrap();
 
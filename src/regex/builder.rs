@@ -1,17 +1,22 @@
-use ::regex::Regex;
-use lazy_static::lazy_static;
 use regex_charclass::irange::range::AnyRange;
-use regex_syntax::ParserBuilder;
+use regex_syntax::{
+    hir::{Capture, ClassUnicodeRange, Repetition},
+    utf8::Utf8Sequences,
+    ParserBuilder,
+};
 
 use super::*;
 
-lazy_static! {
-    static ref RE_FLAG_DETECTION: Regex =
-        Regex::new(r"\(\?[imsx]*-?[imsx]*\)").expect("Can not compile flag detection regex.");
-}
-
 impl RegularExpression {
     pub fn new(regex: &str) -> Result<Self, EngineError> {
+        Self::new_with_dot_mode(regex, true)
+    }
+
+    /// Parse the given pattern, where `.` matches `\n` only if `dot_matches_new_line` is `true` or if the `s` flag is set.
+    pub(crate) fn new_with_dot_mode(
+        regex: &str,
+        dot_matches_new_line: bool,
+    ) -> Result<Self, EngineError> {
         if regex.is_empty() {
             return Ok(RegularExpression::new_empty_string());
         }
@@ -19,17 +24,38 @@ impl RegularExpression {
             return Ok(RegularExpression::new_empty());
         }
         match ParserBuilder::new()
-            .dot_matches_new_line(true)
+            .dot_matches_new_line(dot_matches_new_line)
             .build()
-            .parse(&Self::remove_flags(regex))
+            .parse(regex)
         {
             Ok(hir) => Self::convert_to_regex(&hir),
             Err(err) => Err(EngineError::RegexSyntaxError(err.to_string())),
         }
     }
 
-    fn remove_flags(regex: &str) -> String {
-        RE_FLAG_DETECTION.replace_all(regex, "").to_string()
+    /// Parse the given pattern in byte mode, with the semantics of `regex::bytes`.
+    ///
+    /// The resulting regular expression is over the byte alphabet: the byte `b` is represented by the character `U+00b`, and the Unicode characters of the pattern are encoded in UTF-8.
+    pub fn new_bytes(regex: &str) -> Result<Self, EngineError> {
+        if regex.is_empty() {
+            return Ok(RegularExpression::new_empty_string());
+        }
+        match ParserBuilder::new()
+            .utf8(false)
+            .dot_matches_new_line(true)
+            .build()
+            .parse(regex)
+        {
+            Ok(hir) => {
+                if hir.properties().look_set().contains_word_unicode() {
+                    return Err(EngineError::RegexSyntaxError(
+                        "Unicode word boundaries are not supported in byte mode, use (?-u:\\b) instead".to_string(),
+                    ));
+                }
+                Self::convert_to_regex(&Self::to_byte_hir(&hir))
+            }
+            Err(err) => Err(EngineError::RegexSyntaxError(err.to_string())),
+        }
     }
 
     pub fn new_total() -> Self {
@@ -48,7 +74,10 @@ impl RegularExpression {
         RegularExpression::Concat(VecDeque::new())
     }
 
-    fn convert_to_regex(hir: &Hir) -> Result<Self, EngineError> {
+    pub(super) fn convert_to_regex(hir: &Hir) -> Result<Self, EngineError> {
+        if !hir.properties().look_set().is_empty() {
+            return Self::convert_look_around_to_regex(hir);
+        }
         match hir.kind() {
             HirKind::Empty => Ok(RegularExpression::new_empty_string()),
             HirKind::Literal(literal) => {
@@ -77,7 +106,9 @@ impl RegularExpression {
                     Ok(RegularExpression::Character(range))
                 }
             },
-            HirKind::Look(_) => Ok(RegularExpression::new_empty_string()),
+            HirKind::Look(_) => {
+                unreachable!("Look-arounds are handled by convert_look_around_to_regex.")
+            }
             HirKind::Repetition(repetition) => {
                 let (min, max) = (repetition.min, repetition.max);
                 Self::convert_to_regex(&repetition.sub).map(|v| v.repeat(min, max))
@@ -104,6 +135,56 @@ impl RegularExpression {
         }
     }
 
+    /// Rewrite the given byte-oriented `Hir` so that each byte `b` becomes the character `U+00b`.
+    fn to_byte_hir(hir: &Hir) -> Hir {
+        match hir.kind() {
+            HirKind::Empty | HirKind::Look(_) => hir.clone(),
+            HirKind::Literal(literal) => {
+                let string: String = literal.0.iter().map(|byte| *byte as char).collect();
+                Hir::literal(string.into_bytes())
+            }
+            HirKind::Class(Class::Bytes(class_bytes)) => Hir::class(Class::Unicode(
+                ClassUnicode::new(class_bytes.ranges().iter().map(|range| {
+                    ClassUnicodeRange::new(range.start() as char, range.end() as char)
+                })),
+            )),
+            HirKind::Class(Class::Unicode(class_unicode)) => {
+                let mut sequences = vec![];
+                for range in class_unicode.ranges() {
+                    for sequence in Utf8Sequences::new(range.start(), range.end()) {
+                        sequences.push(Hir::concat(
+                            sequence
+                                .as_slice()
+                                .iter()
+                                .map(|range| {
+                                    Hir::class(Class::Unicode(ClassUnicode::new([
+                                        ClassUnicodeRange::new(
+                                            range.start as char,
+                                            range.end as char,
+                                        ),
+                                    ])))
+                                })
+                                .collect(),
+                        ));
+                    }
+                }
+                Hir::alternation(sequences)
+            }
+            HirKind::Repetition(repetition) => Hir::repetition(Repetition {
+                sub: Box::new(Self::to_byte_hir(&repetition.sub)),
+                ..repetition.clone()
+            }),
+            HirKind::Capture(capture) => Hir::capture(Capture {
+                sub: Box::new(Self::to_byte_hir(&capture.sub)),
+                ..capture.clone()
+            }),
+            HirKind::Concat(concat) => Hir::concat(concat.iter().map(Self::to_byte_hir).collect()),
+            HirKind::Alternation(alternation) => {
+                Hir::alternation(alternation.iter().map(Self::to_byte_hir).collect())
+            }
+        }
+    }
+
     fn to_range_unicode(class_unicode: &ClassUnicode) -> Range {
         let mut new_range = Vec::with_capacity(class_unicode.ranges().len());
         for range in class_unicode.ranges() {
@@ -255,18 +336,73 @@ mod tests {
         let automaton = regex_parsed.to_automaton().unwrap();
 
         assert!(automaton.match_string("a"));
-        assert!(!automaton.match_string("A"));
+        assert!(automaton.match_string("A"));
 
         let regex_parsed = RegularExpression::new("a(?i)a(?-s).").unwrap();
         let automaton = regex_parsed.to_automaton().unwrap();
 
-        assert!(automaton.match_string("aa\n"));
-        assert!(!automaton.match_string("aAb"));
+        assert!(!automaton.match_string("aa\n"));
+        assert!(automaton.match_string("aAb"));
+        assert!(!automaton.match_string("AAb"));
+
+        let regex_parsed = RegularExpression::new("(?i:k)b").unwrap();
+        let automaton = regex_parsed.to_automaton().unwrap();
+
+        assert!(automaton.match_string("Kb"));
+        assert!(automaton.match_string("\u{212A}b"));
+        assert!(!automaton.match_string("kB"));
+
+        let regex_parsed = RegularExpression::new("(?x) a b # comment\n c").unwrap();
+        let automaton = regex_parsed.to_automaton().unwrap();
+
+        assert!(automaton.match_string("abc"));
+        assert!(!automaton.match_string("a b"));
 
         assert!(RegularExpression::new("\\1").is_err());
         Ok(())
     }
 
+    #[test]
+    fn test_new_bytes() -> Result<(), String> {
+        let automaton = RegularExpression::new_bytes("(?-u)[\\x80-\\xff]a")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert!(automaton.match_string("\u{80}a"));
+        assert!(automaton.match_string("\u{FF}a"));
+        assert!(!automaton.match_string("\u{100}a"));
+
+        let automaton = RegularExpression::new_bytes("é+|\\d")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert!(automaton.match_string("\u{C3}\u{A9}\u{C3}\u{A9}"));
+        assert!(automaton.match_string("7"));
+        assert!(automaton.match_string("\u{D9}\u{A3}")); // ARABIC-INDIC DIGIT THREE
+        assert!(!automaton.match_string("é"));
+
+        let automaton = RegularExpression::new_bytes(".")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert!(automaton.match_string("\u{F0}\u{9F}\u{98}\u{80}"));
+        assert!(!automaton.match_string("\u{80}"));
+
+        let automaton = RegularExpression::new_bytes("(?-u:\\b)a+$")
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+
+        assert!(automaton.match_string("aa"));
+
+        assert!(RegularExpression::new_bytes("\\ba").is_err());
+        assert!(RegularExpression::new("(?-u)[\\x80-\\xff]").is_err());
+        Ok(())
+    }
+
     /*#[test]
     fn test_parse_1() -> Result<(), String> {
         let regex_parsed = RegularExpression::new("abc(?=def)").unwrap();
@@ -0,0 +1,220 @@
+use ahash::AHashSet;
+
+use crate::{error::EngineError, fast_automaton::spanning_set::SpanningSet};
+
+use super::*;
+
+impl RegularExpression {
+    /// Compute the Brzozowski derivative of the regular expression by the characters of the given range: the strings `w` such that `cw` is matched for a character `c` of the range.
+    ///
+    /// The given range is expected to be included in one of the classes returned by [`RegularExpression::get_derivative_classes`], all of its characters then have the same derivative.
+    pub fn derivative(&self, range: &Range) -> RegularExpression {
+        match self {
+            RegularExpression::Character(character) => {
+                if character.intersection(range).is_empty() {
+                    RegularExpression::new_empty()
+                } else {
+                    RegularExpression::new_empty_string()
+                }
+            }
+            RegularExpression::Repetition(regular_expression, min, max_opt) => {
+                if max_opt == &Some(0) {
+                    return RegularExpression::new_empty();
+                }
+                regular_expression.derivative(range).concat(
+                    &regular_expression.repeat(min.saturating_sub(1), max_opt.map(|max| max - 1)),
+                    true,
+                )
+            }
+            RegularExpression::Concat(elements) => {
+                let mut regex = RegularExpression::new_empty();
+                for (i, element) in elements.iter().enumerate() {
+                    let tail = elements
+                        .iter()
+                        .skip(i + 1)
+                        .fold(RegularExpression::new_empty_string(), |tail, e| {
+                            tail.concat(e, true)
+                        });
+                    regex = regex.union(&element.derivative(range).concat(&tail, true));
+                    if !element.accepts_empty_string() {
+                        break;
+                    }
+                }
+                regex
+            }
+            RegularExpression::Alternation(elements) => elements
+                .iter()
+                .fold(RegularExpression::new_empty(), |regex, element| {
+                    regex.union(&element.derivative(range))
+                }),
+        }
+    }
+
+    /// Return the classes of characters having the same derivative, for the regular expression and all of its derivatives.
+    pub fn get_derivative_classes(&self) -> Vec<Range> {
+        let mut ranges = vec![];
+        self.collect_ranges(&mut ranges);
+        SpanningSet::compute_spanning_set(&ranges).get_spanning_ranges_with_rest()
+    }
+
+    fn collect_ranges(&self, ranges: &mut Vec<Range>) {
+        match self {
+            RegularExpression::Character(range) => ranges.push(range.clone()),
+            RegularExpression::Repetition(regular_expression, _, _) => {
+                regular_expression.collect_ranges(ranges)
+            }
+            RegularExpression::Concat(elements) => elements
+                .iter()
+                .for_each(|element| element.collect_ranges(ranges)),
+            RegularExpression::Alternation(elements) => elements
+                .iter()
+                .for_each(|element| element.collect_ranges(ranges)),
+        }
+    }
+
+    /// Return `true` if the given string is matched, by taking successive derivatives without building an automaton.
+    pub fn match_string(&self, input: &str) -> bool {
+        let mut regex = Cow::Borrowed(self);
+        for character in input.chars() {
+            let character = Char::new(character);
+            regex = Cow::Owned(regex.derivative(&Range::new_from_range(character..=character)));
+            if regex.is_empty() {
+                return false;
+            }
+        }
+        regex.accepts_empty_string()
+    }
+
+    /// Compute if the two regular expressions have a non-empty intersection, by exploring their derivatives lazily without building automata.
+    pub fn has_intersection(&self, other: &RegularExpression) -> Result<bool, EngineError> {
+        self.find_derivative_pair(
+            other,
+            |nullable_1, nullable_2| nullable_1 && nullable_2,
+            |_, regex_2| regex_2.is_empty(),
+        )
+    }
+
+    /// Compute if the current regular expression is a subset of the given one, by exploring their derivatives lazily without building automata.
+    pub fn is_subset_of(&self, other: &RegularExpression) -> Result<bool, EngineError> {
+        Ok(!self.find_derivative_pair(
+            other,
+            |nullable_1, nullable_2| nullable_1 && !nullable_2,
+            |_, _| false,
+        )?)
+    }
+
+    /// Return `true` if a pair of derivatives of the two regular expressions by the same string satisfies the given predicate on their nullability.
+    ///
+    /// The pairs for which `prune` returns `true` are not explored, a pair whose first derivative is empty never is.
+    fn find_derivative_pair(
+        &self,
+        other: &RegularExpression,
+        predicate: impl Fn(bool, bool) -> bool,
+        prune: impl Fn(&RegularExpression, &RegularExpression) -> bool,
+    ) -> Result<bool, EngineError> {
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+        let max_number_of_states = ThreadLocalParams::get_max_number_of_states();
+
+        let mut ranges = vec![];
+        self.collect_ranges(&mut ranges);
+        other.collect_ranges(&mut ranges);
+        let classes = SpanningSet::compute_spanning_set(&ranges).get_spanning_ranges_with_rest();
+
+        let mut worklist = VecDeque::from([(self.clone(), other.clone())]);
+        let mut seen = AHashSet::from([(self.clone(), other.clone())]);
+        while let Some((regex_1, regex_2)) = worklist.pop_front() {
+            execution_profile.assert_not_timed_out()?;
+            if predicate(
+                regex_1.accepts_empty_string(),
+                regex_2.accepts_empty_string(),
+            ) {
+                return Ok(true);
+            }
+            for class in &classes {
+                let pair = (regex_1.derivative(class), regex_2.derivative(class));
+                if pair.0.is_empty() || prune(&pair.0, &pair.1) || seen.contains(&pair) {
+                    continue;
+                }
+                if seen.len() >= max_number_of_states {
+                    return Err(EngineError::AutomatonHasTooManyStates);
+                }
+                seen.insert(pair.clone());
+                worklist.push_back(pair);
+            }
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex_charclass::char::Char;
+
+    use crate::{regex::RegularExpression, Range};
+
+    #[test]
+    fn test_derivative() -> Result<(), String> {
+        assert_derivative("abc", 'a', "bc");
+        assert_derivative("abc", 'b', "[]");
+        assert_derivative("(ab|ac)*d", 'a', "[bc](ab|ac)*d");
+        assert_derivative("a*b?c", 'c', "");
+        assert_derivative("a{2,4}", 'a', "a{1,3}");
+        assert_derivative("(a?b){2}", 'b', "a?b");
+        Ok(())
+    }
+
+    fn assert_derivative(regex: &str, character: char, expected: &str) {
+        let regex = RegularExpression::new(regex).unwrap();
+        let character = Char::new(character);
+        let derivative = regex.derivative(&Range::new_from_range(character..=character));
+
+        let expected = RegularExpression::new(expected).unwrap();
+        assert!(
+            derivative
+                .to_automaton()
+                .unwrap()
+                .is_equivalent_of(&expected.to_automaton().unwrap())
+                .unwrap(),
+            "{regex} {derivative}"
+        );
+    }
+
+    #[test]
+    fn test_match_string() -> Result<(), String> {
+        let regex = RegularExpression::new(".{0,5000}abc").unwrap();
+        assert!(regex.match_string("abc"));
+        assert!(regex.match_string(&format!("{}abc", "x".repeat(5000))));
+        assert!(!regex.match_string(&format!("{}abc", "x".repeat(5001))));
+        assert!(!regex.match_string("abcd"));
+
+        let regex = RegularExpression::new("(ab|c)*").unwrap();
+        assert!(regex.match_string(""));
+        assert!(regex.match_string("cabab"));
+        assert!(!regex.match_string("cba"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_intersection_and_subset() -> Result<(), String> {
+        let regex_1 = RegularExpression::new(".{0,5000}abc").unwrap();
+        let regex_2 = RegularExpression::new("x*ab[a-c]").unwrap();
+        let regex_3 = RegularExpression::new("x*abd").unwrap();
+
+        assert!(regex_1.has_intersection(&regex_2).unwrap());
+        assert!(!RegularExpression::new(".{0,1000}abc")
+            .unwrap()
+            .has_intersection(&regex_3)
+            .unwrap());
+        assert!(!regex_2.is_subset_of(&regex_1).unwrap());
+
+        let regex_4 = RegularExpression::new("x{0,10}abc").unwrap();
+        assert!(regex_4.is_subset_of(&regex_1).unwrap());
+        assert!(!regex_1.is_subset_of(&regex_4).unwrap());
+
+        let regex_5 = RegularExpression::new("(ab)*").unwrap();
+        let regex_6 = RegularExpression::new("a(ba)*b|").unwrap();
+        assert!(regex_5.is_subset_of(&regex_6).unwrap());
+        assert!(regex_6.is_subset_of(&regex_5).unwrap());
+        Ok(())
+    }
+}
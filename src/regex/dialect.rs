@@ -0,0 +1,432 @@
+use regex_charclass::char::Char;
+
+use crate::error::EngineError;
+
+use super::*;
+
+/// A syntax in which a [`RegularExpression`] can be parsed with [`RegularExpression::new_with_dialect`] or written with [`RegularExpression::to_dialect`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Dialect {
+    /// The syntax of the Rust `regex` crate.
+    Rust,
+    /// Perl Compatible Regular Expressions, in UTF mode.
+    Pcre,
+    /// JavaScript regular expressions without the `u` flag, matching UTF-16 code units.
+    EcmaScript,
+    /// JavaScript regular expressions with the `u` flag.
+    EcmaScriptUnicode,
+    /// The syntax of `java.util.regex`.
+    Java,
+    /// The syntax of the Python `re` module, for `str` patterns.
+    Python,
+    /// POSIX Extended Regular Expressions, in a UTF-8 locale.
+    PosixEre,
+    /// The syntax of RE2.
+    Re2,
+}
+
+impl Dialect {
+    /// The largest repetition bound accepted by the dialect.
+    fn get_max_repetition(&self) -> u32 {
+        match self {
+            Dialect::PosixEre => 255,
+            Dialect::Re2 => 1000,
+            Dialect::Pcre => 65535,
+            _ => u32::MAX,
+        }
+    }
+
+    fn group(&self, regex: &str) -> String {
+        match self {
+            Dialect::PosixEre => format!("({regex})"),
+            _ => format!("(?:{regex})"),
+        }
+    }
+
+    fn get_any_character(&self) -> &'static str {
+        match self {
+            Dialect::PosixEre => ".",
+            Dialect::EcmaScriptUnicode => "[\\s\\S]",
+            _ => "(?s:.)",
+        }
+    }
+
+    fn get_empty_language(&self) -> Result<&'static str, EngineError> {
+        match self {
+            Dialect::Rust | Dialect::Re2 => Ok("[^\\x{0}-\\x{10FFFF}]"),
+            Dialect::EcmaScript | Dialect::EcmaScriptUnicode => Ok("[]"),
+            Dialect::Pcre | Dialect::Java | Dialect::Python => Ok("(?!)"),
+            Dialect::PosixEre => Err(EngineError::RegexCannotBeExpressed(String::from(
+                "POSIX ERE can not express the empty language",
+            ))),
+        }
+    }
+
+    /// Return the characters escaped with a backslash, outside and inside a bracket expression.
+    fn get_metacharacters(&self, in_class: bool) -> &'static str {
+        match (self, in_class) {
+            (Dialect::PosixEre, false) => "^.[$()|*+?{\\",
+            (Dialect::PosixEre, true) => "",
+            (Dialect::EcmaScript | Dialect::EcmaScriptUnicode, false) => "\\.+*?()|[]{}^$/",
+            (Dialect::EcmaScript | Dialect::EcmaScriptUnicode, true) => "\\[]^-/",
+            (_, false) => "\\.+*?()|[]{}^$",
+            (Dialect::Rust | Dialect::Java | Dialect::Python, true) => "\\[]^-&~|",
+            (_, true) => "\\[]^-",
+        }
+    }
+
+    fn escape_char(&self, character: char, in_class: bool) -> Result<String, EngineError> {
+        if self.get_metacharacters(in_class).contains(character) {
+            return Ok(format!("\\{character}"));
+        } else if character == ' '
+            || character.is_ascii_graphic()
+            || !character.is_ascii() && character.is_alphanumeric()
+        {
+            return Ok(character.to_string());
+        }
+
+        let code = character as u32;
+        Ok(match self {
+            Dialect::Rust | Dialect::Pcre | Dialect::Java | Dialect::Re2 => {
+                format!("\\x{{{:X}}}", code)
+            }
+            Dialect::EcmaScriptUnicode => format!("\\u{{{:X}}}", code),
+            Dialect::EcmaScript => {
+                let mut units = [0; 2];
+                character
+                    .encode_utf16(&mut units)
+                    .iter()
+                    .map(|unit| format!("\\u{:04X}", unit))
+                    .collect()
+            }
+            Dialect::Python if code < 0x100 => format!("\\x{:02X}", code),
+            Dialect::Python if code < 0x10000 => format!("\\u{:04X}", code),
+            Dialect::Python => format!("\\U{:08X}", code),
+            Dialect::PosixEre if code == 0 => {
+                return Err(EngineError::RegexCannotBeExpressed(String::from(
+                    "POSIX ERE can not express the NUL character",
+                )))
+            }
+            Dialect::PosixEre => character.to_string(),
+        })
+    }
+
+    #[inline]
+    fn is_utf16(&self) -> bool {
+        self == &Dialect::EcmaScript
+    }
+
+    /// Write the given range, return it with `true` if it does not need to be grouped to be repeated.
+    fn write_range(&self, range: &Range) -> Result<(String, bool), EngineError> {
+        if range.is_empty() {
+            return Ok((self.get_empty_language()?.to_string(), true));
+        } else if range.is_total() && !self.is_utf16() {
+            return Ok((self.get_any_character().to_string(), true));
+        }
+        match self {
+            Dialect::EcmaScript => self.write_utf16_range(range),
+            Dialect::PosixEre => Ok((self.write_bracket_expression(range)?, true)),
+            _ => {
+                let bounds = get_bounds(range);
+                if let [(first, last)] = bounds[..] {
+                    if first == last {
+                        return Ok((self.escape_char(first, false)?, true));
+                    }
+                }
+                let complement = get_bounds(&range.complement());
+                let (bounds, negated) = if complement.len() < bounds.len() {
+                    (complement, "^")
+                } else {
+                    (bounds, "")
+                };
+                Ok((
+                    format!("[{negated}{}]", self.write_class_bounds(&bounds)?),
+                    true,
+                ))
+            }
+        }
+    }
+
+    fn write_class_bounds(&self, bounds: &[(char, char)]) -> Result<String, EngineError> {
+        let mut sb = String::new();
+        for (first, last) in bounds {
+            sb.push_str(&self.escape_char(*first, true)?);
+            if first != last {
+                sb.push('-');
+                sb.push_str(&self.escape_char(*last, true)?);
+            }
+        }
+        Ok(sb)
+    }
+
+    /// Write the given range matching UTF-16 code units, the characters outside of the Basic Multilingual Plane are matched as surrogate pairs.
+    fn write_utf16_range(&self, range: &Range) -> Result<(String, bool), EngineError> {
+        let bmp = Range::new_from_range(Char::new('\0')..=Char::new('\u{FFFF}'));
+
+        let mut alternatives = vec![];
+        // The bounds are split around the surrogates, which would otherwise match halves of surrogate pairs.
+        let bmp_bounds: Vec<(char, char)> = get_bounds(&range.intersection(&bmp))
+            .into_iter()
+            .flat_map(|(first, last)| {
+                if first <= '\u{D7FF}' && last >= '\u{E000}' {
+                    vec![(first, '\u{D7FF}'), ('\u{E000}', last)]
+                } else {
+                    vec![(first, last)]
+                }
+            })
+            .collect();
+        if let [(first, last)] = bmp_bounds[..] {
+            if first == last {
+                alternatives.push(self.escape_char(first, false)?);
+            }
+        }
+        if alternatives.is_empty() && !bmp_bounds.is_empty() {
+            alternatives.push(format!("[{}]", self.write_class_bounds(&bmp_bounds)?));
+        }
+        for (first, last) in get_bounds(&range.difference(&bmp)) {
+            let (mut first_high, first_low) = get_surrogates(first);
+            let (mut last_high, last_low) = get_surrogates(last);
+            if first_high == last_high {
+                alternatives.push(write_surrogates(
+                    first_high, first_high, first_low, last_low,
+                ));
+                continue;
+            }
+            if first_low != 0xDC00 {
+                alternatives.push(write_surrogates(first_high, first_high, first_low, 0xDFFF));
+                first_high += 1;
+            }
+            let last_alternative = if last_low != 0xDFFF {
+                last_high -= 1;
+                Some(write_surrogates(
+                    last_high + 1,
+                    last_high + 1,
+                    0xDC00,
+                    last_low,
+                ))
+            } else {
+                None
+            };
+            if first_high <= last_high {
+                alternatives.push(write_surrogates(first_high, last_high, 0xDC00, 0xDFFF));
+            }
+            alternatives.extend(last_alternative);
+        }
+
+        if alternatives.len() > 1 {
+            Ok((self.group(&alternatives.join("|")), true))
+        } else {
+            let is_code_unit = bmp_bounds.len() == 1;
+            Ok((alternatives.remove(0), is_code_unit))
+        }
+    }
+
+    /// Write the given range as a POSIX bracket expression, where `]` has to come first, `-` last and `^` anywhere but first.
+    fn write_bracket_expression(&self, range: &Range) -> Result<String, EngineError> {
+        if let [(first, last)] = get_bounds(range)[..] {
+            if first == last {
+                return self.escape_char(first, false);
+            }
+        }
+        // The NUL character can not be written, a range containing it is written as the complement of its complement.
+        let contains_nul = range.contains(Char::new('\0'));
+        let (range, negated) = if contains_nul {
+            (range.complement(), "^")
+        } else {
+            (range.clone(), "")
+        };
+
+        let special_characters =
+            [']', '^', '-']
+                .iter()
+                .fold(Range::empty(), |special_characters, character| {
+                    let character = Char::new(*character);
+                    special_characters.union(&Range::new_from_range(character..=character))
+                });
+        let mut sb = String::from("[");
+        sb.push_str(negated);
+        if range.contains(Char::new(']')) {
+            sb.push(']');
+        }
+        sb.push_str(&self.write_class_bounds(&get_bounds(&range.difference(&special_characters)))?);
+        if range.contains(Char::new('^')) {
+            sb.push('^');
+        }
+        if range.contains(Char::new('-')) {
+            sb.push('-');
+        }
+        sb.push(']');
+        Ok(sb)
+    }
+}
+
+impl RegularExpression {
+    /// Write the regular expression in the syntax of the given dialect.
+    ///
+    /// Returns an error if the regular expression uses a construct that can not be expressed in the dialect.
+    pub fn to_dialect(&self, dialect: Dialect) -> Result<String, EngineError> {
+        Ok(self.write_dialect(dialect)?.0)
+    }
+
+    /// Write the regular expression in the given dialect, return it with `true` if it does not need to be grouped to be repeated.
+    fn write_dialect(&self, dialect: Dialect) -> Result<(String, bool), EngineError> {
+        Ok(match self {
+            RegularExpression::Character(range) => dialect.write_range(range)?,
+            RegularExpression::Repetition(regular_expression, min, max_opt) => {
+                let max_repetition = dialect.get_max_repetition();
+                if *min > max_repetition || max_opt.is_some_and(|max| max > max_repetition) {
+                    return Err(EngineError::RegexCannotBeExpressed(format!(
+                        "The dialect {dialect:?} does not support repetitions above {max_repetition}"
+                    )));
+                }
+
+                let (regex_part, is_atomic) = regular_expression.write_dialect(dialect)?;
+                let regex_part = match **regular_expression {
+                    RegularExpression::Repetition(_, _, _) | RegularExpression::Concat(_) => {
+                        dialect.group(&regex_part)
+                    }
+                    _ if !is_atomic => dialect.group(&regex_part),
+                    _ => regex_part,
+                };
+                let multiplicator_part = match (min, max_opt) {
+                    (0, None) => String::from("*"),
+                    (1, None) => String::from("+"),
+                    (0, Some(1)) => String::from("?"),
+                    (min, None) => format!("{{{},}}", min),
+                    (min, Some(max)) if min == max => format!("{{{}}}", max),
+                    (min, Some(max)) => format!("{{{},{}}}", min, max),
+                };
+                (format!("{}{}", regex_part, multiplicator_part), true)
+            }
+            RegularExpression::Concat(elements) => {
+                let mut sb = String::new();
+                for element in elements {
+                    sb.push_str(&element.write_dialect(dialect)?.0);
+                }
+                (sb, false)
+            }
+            RegularExpression::Alternation(elements) => match &elements[..] {
+                [] => (dialect.get_empty_language()?.to_string(), true),
+                [element] => element.write_dialect(dialect)?,
+                _ => {
+                    let elements = elements
+                        .iter()
+                        .map(|element| Ok(element.write_dialect(dialect)?.0))
+                        .collect::<Result<Vec<_>, EngineError>>()?;
+                    (dialect.group(&elements.join("|")), true)
+                }
+            },
+        })
+    }
+}
+
+fn get_bounds(range: &Range) -> Vec<(char, char)> {
+    range
+        .0
+        .chunks_exact(2)
+        .map(|bounds| (bounds[0].to_char(), bounds[1].to_char()))
+        .collect()
+}
+
+fn get_surrogates(character: char) -> (u32, u32) {
+    let code = character as u32 - 0x10000;
+    (0xD800 + (code >> 10), 0xDC00 + (code & 0x3FF))
+}
+
+fn write_surrogates(first_high: u32, last_high: u32, first_low: u32, last_low: u32) -> String {
+    let write_units = |first: u32, last: u32| {
+        if first == last {
+            format!("\\u{:04X}", first)
+        } else {
+            format!("[\\u{:04X}-\\u{:04X}]", first, last)
+        }
+    };
+    format!(
+        "{}{}",
+        write_units(first_high, last_high),
+        write_units(first_low, last_low)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::{Dialect, RegularExpression};
+
+    #[test]
+    fn test_to_dialect() -> Result<(), String> {
+        assert_to_dialect("(ab|c)*d{2,5}", Dialect::Java, "(?:c|ab)*d{2,5}");
+        assert_to_dialect("(ab|c)*d{2,5}", Dialect::PosixEre, "(c|ab)*d{2,5}");
+        assert_to_dialect("a.b", Dialect::Rust, "a(?s:.)b");
+        assert_to_dialect("a.b", Dialect::EcmaScriptUnicode, "a[\\s\\S]b");
+        assert_to_dialect(
+            "a.b",
+            Dialect::EcmaScript,
+            "a(?:[\\u0000-\\uD7FF\\uE000-\\uFFFF]|[\\uD800-\\uDBFF][\\uDC00-\\uDFFF])b",
+        );
+        assert_to_dialect("[]a-]\\^x/", Dialect::PosixEre, "[]a-]\\^x/");
+        assert_to_dialect("[]a-]\\^x/", Dialect::EcmaScript, "[\\-\\]a]\\^x\\/");
+        assert_to_dialect("[a-z~]", Dialect::Rust, "[a-z\\~]");
+        assert_to_dialect("[a-z~]", Dialect::Re2, "[a-z~]");
+
+        assert_to_dialect("\\n\\x00é", Dialect::Pcre, "\\x{A}\\x{0}é");
+        assert_to_dialect("\\n\\x00é", Dialect::Python, "\\x0A\\x00é");
+        assert_to_dialect("\\n\\x00é", Dialect::EcmaScriptUnicode, "\\u{A}\\u{0}é");
+
+        assert_to_dialect("😀+", Dialect::Java, "\\x{1F600}+");
+        assert_to_dialect("😀+", Dialect::Python, "\\U0001F600+");
+        assert_to_dialect("😀+", Dialect::EcmaScript, "(?:\\uD83D\\uDE00)+");
+        assert_to_dialect("😀+", Dialect::PosixEre, "😀+");
+        assert_to_dialect(
+            "[a\\u{1F600}-\\u{1F6FF}\\u{1F900}-\\u{1FAFF}]",
+            Dialect::EcmaScript,
+            "(?:a|\\uD83D[\\uDE00-\\uDEFF]|\\uD83E[\\uDD00-\\uDEFF])",
+        );
+
+        assert_to_dialect("[]", Dialect::Re2, "[^\\x{0}-\\x{10FFFF}]");
+        assert_to_dialect("[]", Dialect::Python, "(?!)");
+        assert_to_dialect("[]", Dialect::EcmaScript, "[]");
+        assert_to_dialect("[^a]", Dialect::PosixEre, "[^a]");
+        assert_to_dialect("a{300}", Dialect::Pcre, "a{300}");
+
+        assert_not_expressible("[]", Dialect::PosixEre);
+        assert_not_expressible("a\\x00", Dialect::PosixEre);
+        assert_not_expressible("a{300}", Dialect::PosixEre);
+        assert_not_expressible("a{2000}", Dialect::Re2);
+        Ok(())
+    }
+
+    fn assert_to_dialect(regex: &str, dialect: Dialect, expected: &str) {
+        let regex = RegularExpression::new(regex).unwrap();
+        assert_eq!(expected, regex.to_dialect(dialect).unwrap());
+    }
+
+    fn assert_not_expressible(regex: &str, dialect: Dialect) {
+        let regex = RegularExpression::new(regex).unwrap();
+        assert!(regex.to_dialect(dialect).is_err(), "{regex}");
+    }
+
+    #[test]
+    fn test_to_rust_dialect_is_equivalent() -> Result<(), String> {
+        for regex in [
+            "(ab|c)*d{2,5}",
+            "[^a-z]+\\.(x|\\\\)?",
+            "[\\-\\]\\[a]\\^x[&~|]",
+            "\\n\\t\\x00(a{2,}){0,3}",
+            "[a\\u{1F600}-\\u{1F6FF}]é.",
+            "[]",
+        ] {
+            let regex = RegularExpression::new(regex).unwrap();
+            let written =
+                RegularExpression::new(&regex.to_dialect(Dialect::Rust).unwrap()).unwrap();
+            assert!(
+                regex
+                    .to_automaton()
+                    .unwrap()
+                    .is_equivalent_of(&written.to_automaton().unwrap())
+                    .unwrap(),
+                "{regex} {written}"
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,1079 @@
+use std::{fmt::Write, ops::RangeInclusive};
+
+use crate::error::EngineError;
+
+use super::*;
+
+/// The characters matched by `\h` in PCRE and Java.
+const HORIZONTAL_SPACES: &str =
+    "\\x{9}\\x{20}\\x{A0}\\x{1680}\\x{180E}\\x{2000}-\\x{200A}\\x{202F}\\x{205F}\\x{3000}";
+/// The characters matched by `\v` in PCRE and Java.
+const VERTICAL_SPACES: &str = "\\x{A}-\\x{D}\\x{85}\\x{2028}\\x{2029}";
+const ASCII_DIGITS: &str = "0-9";
+const ASCII_WORD_CHARACTERS: &str = "0-9A-Za-z_";
+const ECMASCRIPT_SPACES: &str = "\\x{9}-\\x{D}\\x{20}\\x{A0}\\x{1680}\\x{2000}-\\x{200A}\\x{2028}\\x{2029}\\x{202F}\\x{205F}\\x{3000}\\x{FEFF}";
+const PCRE_SPACES: &str = "\\x{9}-\\x{D}\\x{20}";
+const RE2_SPACES: &str = "\\x{9}\\x{A}\\x{C}\\x{D}\\x{20}";
+
+/// The POSIX classes of Java, written `\p{Name}`, with the name of the corresponding ASCII class.
+const JAVA_POSIX_CLASSES: [(&str, &str); 13] = [
+    ("Lower", "lower"),
+    ("Upper", "upper"),
+    ("ASCII", "ascii"),
+    ("Alpha", "alpha"),
+    ("Digit", "digit"),
+    ("Alnum", "alnum"),
+    ("Punct", "punct"),
+    ("Graph", "graph"),
+    ("Print", "print"),
+    ("Blank", "blank"),
+    ("Cntrl", "cntrl"),
+    ("XDigit", "xdigit"),
+    ("Space", "space"),
+];
+
+impl RegularExpression {
+    /// Parse the given pattern written in the syntax of the given dialect.
+    ///
+    /// The constructs specific to the dialect, such as `\h` or `\cX` in PCRE, `\u{...}` in ECMAScript or the POSIX classes in bracket expressions, are normalized into the syntax of [`RegularExpression::new`], whose semantics are kept for the anchors, which refer to the whole text. Unless the `s` flag is set, `.` does not match the line terminators of the dialect: `\n` in PCRE, Python, RE2 and Rust, `\n`, `\r`, `\u2028` and `\u2029` in ECMAScript, and `\u0085` too in Java. In POSIX ERE, `.` matches any character.
+    ///
+    /// Returns [`EngineError::RegexSyntaxError`] if the pattern uses a construct that does not describe a regular language, like a backreference, or that is not supported, like a lookaround.
+    pub fn new_with_dialect(regex: &str, dialect: Dialect) -> Result<Self, EngineError> {
+        if dialect == Dialect::Rust {
+            return Self::new_with_dot_mode(regex, false);
+        }
+        let normalized = DialectParser {
+            dialect,
+            pattern: regex.chars().collect(),
+            position: 0,
+        }
+        .parse()?;
+        Self::new(&normalized)
+    }
+}
+
+/// The modes changed by the inline flags, restored when the enclosing group is closed.
+#[derive(Clone, Copy, Default)]
+struct Modes {
+    /// The `x` flag, the whitespaces and the comments are ignored.
+    extended: bool,
+    /// The `s` flag, `.` matches the line terminators.
+    dot_matches_new_line: bool,
+}
+
+/// An element of the normalized pattern.
+enum Item {
+    /// A literal character, which can be the bound of a range in a character class.
+    Character(char),
+    /// A fragment already written in the syntax of the `regex` crate.
+    Raw(String),
+}
+
+/// Translate a pattern written in a dialect into the syntax of the `regex` crate.
+struct DialectParser {
+    dialect: Dialect,
+    pattern: Vec<char>,
+    position: usize,
+}
+
+impl DialectParser {
+    fn parse(&mut self) -> Result<String, EngineError> {
+        let mut regex = String::with_capacity(self.pattern.len());
+        // The modes of the enclosing groups, restored when they are closed.
+        let mut groups = vec![];
+        let mut modes = Modes::default();
+        while let Some(character) = self.peek() {
+            let start = self.position;
+            if let Some(pairs) = self.parse_surrogate_pairs() {
+                regex.push_str(&pairs);
+                continue;
+            }
+            match character {
+                '\\' if self.is_quote() => regex.push_str(&self.parse_quote()),
+                '\\' => {
+                    let item = self.parse_escape(false)?;
+                    Self::push_item(&mut regex, item);
+                }
+                '[' => regex.push_str(&self.parse_class()?),
+                '(' => self.parse_group(&mut regex, &mut groups, &mut modes)?,
+                ')' => {
+                    self.position += 1;
+                    if let Some(enclosing) = groups.pop() {
+                        modes = enclosing;
+                    }
+                    regex.push(')');
+                }
+                '*' | '+' | '?' => {
+                    self.position += 1;
+                    regex.push(character);
+                    self.parse_quantifier_mode(&mut regex, start)?;
+                }
+                '{' => match self.parse_bounds() {
+                    Some(bounds) => {
+                        regex.push_str(&bounds);
+                        self.parse_quantifier_mode(&mut regex, start)?;
+                    }
+                    None if matches!(self.dialect, Dialect::Java | Dialect::EcmaScriptUnicode) => {
+                        self.position += 1;
+                        return Err(self.invalid("Invalid quantifier", start));
+                    }
+                    None => {
+                        self.position += 1;
+                        Self::push_literal(&mut regex, character);
+                    }
+                },
+                '.' if !modes.dot_matches_new_line => {
+                    self.position += 1;
+                    regex.push_str(self.get_any_character_but_line_terminators());
+                }
+                '|' | '^' | '$' | '.' => {
+                    self.position += 1;
+                    regex.push(character);
+                }
+                '#' if modes.extended => while self.next_char().is_some_and(|c| c != '\n') {},
+                _ if modes.extended && character.is_whitespace() => self.position += 1,
+                _ => {
+                    self.position += 1;
+                    Self::push_literal(&mut regex, character);
+                }
+            }
+        }
+        Ok(regex)
+    }
+
+    fn parse_group(
+        &mut self,
+        regex: &mut String,
+        groups: &mut Vec<Modes>,
+        modes: &mut Modes,
+    ) -> Result<(), EngineError> {
+        let start = self.position;
+        self.position += 1;
+        if self.dialect == Dialect::Pcre && self.peek() == Some('*') {
+            self.skip_past(')');
+            return Err(self.unsupported("backtracking control verb", start));
+        }
+        if self.dialect == Dialect::PosixEre || !self.eat("?") {
+            groups.push(*modes);
+            regex.push('(');
+            return Ok(());
+        }
+
+        if self.eat("#") {
+            if !self.skip_past(')') {
+                return Err(self.invalid("Unclosed comment", start));
+            }
+            return Ok(());
+        } else if self.eat("!)") {
+            // The negative lookahead of the empty string never matches, it is written for the empty language.
+            regex.push_str("[^\\x{0}-\\x{10FFFF}]");
+            return Ok(());
+        } else if self.eat("=") || self.eat("!") {
+            return Err(self.unsupported("lookahead", start));
+        } else if self.eat("<=") || self.eat("<!") {
+            return Err(self.unsupported("lookbehind", start));
+        } else if self.eat(">") {
+            return Err(self.unsupported("atomic group", start));
+        } else if self.peek() == Some('(') {
+            self.skip_past(')');
+            return Err(self.non_regular("conditional group", start));
+        } else if self.eat("P=") {
+            self.skip_past(')');
+            return Err(self.non_regular("backreference", start));
+        } else if self.eat("P>")
+            || self.eat("&")
+            || self.eat("R")
+            || self.peek().is_some_and(|c| c.is_ascii_digit())
+            || (matches!(self.peek(), Some('+' | '-'))
+                && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            self.skip_past(')');
+            return Err(self.non_regular("recursion", start));
+        }
+
+        groups.push(*modes);
+        if self.eat(":") || self.eat("|") {
+            regex.push_str("(?:");
+        } else if self.eat("P<") || self.eat("<") || self.eat("'") {
+            let delimiter = if self.pattern[self.position - 1] == '\'' {
+                '\''
+            } else {
+                '>'
+            };
+            let name_start = self.position;
+            if !self.skip_past(delimiter) {
+                return Err(self.invalid("Unclosed group name", start));
+            }
+            let name: String = self.pattern[name_start..self.position - 1].iter().collect();
+            write!(regex, "(?P<{name}>").unwrap();
+        } else {
+            // The flags are left to the parser of the `regex` crate, only the extended and the dot all modes change the parsing.
+            regex.push_str("(?");
+            let mut enabled = true;
+            let mut flags_modes = *modes;
+            while let Some(flag) = self.peek().filter(|c| c.is_ascii_alphabetic() || *c == '-') {
+                self.position += 1;
+                regex.push(flag);
+                match flag {
+                    '-' => enabled = false,
+                    'x' => flags_modes.extended = enabled,
+                    's' => flags_modes.dot_matches_new_line = enabled,
+                    _ => {}
+                }
+            }
+            if self.eat(":") {
+                regex.push(':');
+            } else if self.eat(")") {
+                groups.pop();
+                regex.push(')');
+            }
+            *modes = flags_modes;
+        }
+        Ok(())
+    }
+
+    /// Return the class matched by `.` when the `s` flag is not set.
+    fn get_any_character_but_line_terminators(&self) -> &'static str {
+        match self.dialect {
+            Dialect::EcmaScript | Dialect::EcmaScriptUnicode => "[^\\n\\r\\x{2028}\\x{2029}]",
+            Dialect::Java => "[^\\n\\r\\x{85}\\x{2028}\\x{2029}]",
+            Dialect::PosixEre => ".",
+            _ => "[^\\n]",
+        }
+    }
+
+    /// Parse the greediness marker following a quantifier starting at `start`.
+    fn parse_quantifier_mode(
+        &mut self,
+        regex: &mut String,
+        start: usize,
+    ) -> Result<(), EngineError> {
+        match self.peek() {
+            Some('?') if self.dialect != Dialect::PosixEre => {
+                self.position += 1;
+                regex.push('?');
+            }
+            Some('+') if self.dialect != Dialect::PosixEre => {
+                self.position += 1;
+                return Err(self.unsupported("possessive quantifier", start));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Parse the bounds of a repetition, `None` if the brace does not start a valid quantifier.
+    fn parse_bounds(&mut self) -> Option<String> {
+        let start = self.position;
+        self.position += 1;
+        let min = self.read_digits(10, usize::MAX);
+        let max = if self.eat(",") {
+            Some(self.read_digits(10, usize::MAX))
+        } else {
+            None
+        };
+        if !self.eat("}") {
+            self.position = start;
+            return None;
+        }
+        let accepts_missing_min = matches!(self.dialect, Dialect::Pcre | Dialect::Python);
+        match (min, max) {
+            (Some(min), None) => Some(format!("{{{min}}}")),
+            (Some(min), Some(None)) => Some(format!("{{{min},}}")),
+            (Some(min), Some(Some(max))) => Some(format!("{{{min},{max}}}")),
+            (None, Some(Some(max))) if accepts_missing_min => Some(format!("{{0,{max}}}")),
+            _ => {
+                self.position = start;
+                None
+            }
+        }
+    }
+
+    fn parse_class(&mut self) -> Result<String, EngineError> {
+        let start = self.position;
+        self.position += 1;
+        let mut class = String::from("[");
+        let negated = self.eat("^");
+        if negated {
+            class.push('^');
+        }
+        if matches!(
+            self.dialect,
+            Dialect::EcmaScript | Dialect::EcmaScriptUnicode
+        ) && self.eat("]")
+        {
+            return Ok(String::from(if negated {
+                "[\\x{0}-\\x{10FFFF}]"
+            } else {
+                "[^\\x{0}-\\x{10FFFF}]"
+            }));
+        }
+
+        let mut first = true;
+        loop {
+            match self.peek() {
+                None => return Err(self.invalid("Unclosed character class", start)),
+                Some(']') if !first => {
+                    self.position += 1;
+                    class.push(']');
+                    return Ok(class);
+                }
+                _ => first = false,
+            }
+            let item_start = self.position;
+            match self.parse_class_item()? {
+                Item::Character(first_character)
+                    if self.peek() == Some('-') && self.peek_at(1).is_some_and(|c| c != ']') =>
+                {
+                    self.position += 1;
+                    match self.parse_class_item()? {
+                        Item::Character(last_character) if first_character <= last_character => {
+                            Self::push_literal(&mut class, first_character);
+                            class.push('-');
+                            Self::push_literal(&mut class, last_character);
+                        }
+                        Item::Character(_) => {
+                            return Err(self.invalid("Invalid character class range", item_start));
+                        }
+                        item => {
+                            Self::push_literal(&mut class, first_character);
+                            Self::push_literal(&mut class, '-');
+                            Self::push_item(&mut class, item);
+                        }
+                    }
+                }
+                item => Self::push_item(&mut class, item),
+            }
+        }
+    }
+
+    fn parse_class_item(&mut self) -> Result<Item, EngineError> {
+        let start = self.position;
+        let character = self.pattern[start];
+        match character {
+            '[' if matches!(
+                self.dialect,
+                Dialect::Pcre | Dialect::Re2 | Dialect::PosixEre
+            ) && matches!(self.peek_at(1), Some(':' | '.' | '=')) =>
+            {
+                let delimiter = self.pattern[start + 1];
+                self.position += 2;
+                let name_start = self.position;
+                while self.position + 1 < self.pattern.len()
+                    && !(self.pattern[self.position] == delimiter
+                        && self.pattern[self.position + 1] == ']')
+                {
+                    self.position += 1;
+                }
+                if self.position + 1 >= self.pattern.len() {
+                    self.position = start + 1;
+                    return Ok(Item::Character('['));
+                }
+                let name: String = self.pattern[name_start..self.position].iter().collect();
+                self.position += 2;
+                let mut characters = name.chars();
+                match (delimiter, characters.next(), characters.next()) {
+                    (':', _, _) => Ok(Item::Raw(format!("[:{name}:]"))),
+                    (_, Some(character), None) => Ok(Item::Character(character)),
+                    _ => Err(self.unsupported("collating element", start)),
+                }
+            }
+            '[' if self.dialect == Dialect::Java => Ok(Item::Raw(self.parse_class()?)),
+            '&' if self.dialect == Dialect::Java && self.peek_at(1) == Some('&') => {
+                self.position += 2;
+                Ok(Item::Raw(String::from("&&")))
+            }
+            '\\' if self.dialect == Dialect::PosixEre => {
+                self.position += 1;
+                Ok(Item::Character('\\'))
+            }
+            '\\' if self.is_quote() => Ok(Item::Raw(self.parse_quote())),
+            '\\' => self.parse_escape(true),
+            _ => {
+                self.position += 1;
+                Ok(Item::Character(character))
+            }
+        }
+    }
+
+    fn parse_escape(&mut self, in_class: bool) -> Result<Item, EngineError> {
+        let start = self.position;
+        self.position += 1;
+        let Some(character) = self.next_char() else {
+            return Err(self.invalid("Incomplete escape sequence", start));
+        };
+        if self.dialect == Dialect::PosixEre {
+            return Ok(Item::Character(character));
+        }
+
+        let is_ecmascript = matches!(
+            self.dialect,
+            Dialect::EcmaScript | Dialect::EcmaScriptUnicode
+        );
+        let is_pcre_or_java = matches!(self.dialect, Dialect::Pcre | Dialect::Java);
+        let has_ascii_classes = self.dialect != Dialect::Python;
+        match character {
+            '0' => {
+                let value = self.read_digits(8, 2).unwrap_or(0);
+                self.code_point(value, start)
+            }
+            '1'..='7'
+                if in_class
+                    || (self.dialect == Dialect::Python
+                        && self.peek().is_some_and(|c| c.is_digit(8))
+                        && self.peek_at(1).is_some_and(|c| c.is_digit(8))) =>
+            {
+                self.position -= 1;
+                let value = self.read_digits(8, 3).unwrap_or(0);
+                self.code_point(value, start)
+            }
+            '1'..='9' if !in_class => {
+                self.read_digits(10, usize::MAX);
+                Err(self.non_regular("backreference", start))
+            }
+            'o' if self.dialect == Dialect::Pcre && self.eat("{") => {
+                match (self.read_digits(8, usize::MAX), self.eat("}")) {
+                    (Some(value), true) => self.code_point(value, start),
+                    _ => Err(self.invalid("Invalid octal escape", start)),
+                }
+            }
+            'x' => self.parse_hexadecimal_escape(start),
+            'u' if is_ecmascript || matches!(self.dialect, Dialect::Java | Dialect::Python) => {
+                self.parse_unicode_escape(start)
+            }
+            'U' if self.dialect == Dialect::Python => match self.read_exact_hexadecimal(8) {
+                Some(value) => self.code_point(value, start),
+                None => Err(self.invalid("Invalid Unicode escape", start)),
+            },
+            'N' if self.dialect == Dialect::Python => {
+                if self.eat("{") {
+                    self.skip_past('}');
+                }
+                Err(self.unsupported("named character escape", start))
+            }
+            'N' if self.dialect == Dialect::Pcre && !in_class => {
+                Ok(Item::Raw(String::from("[^\\x{A}]")))
+            }
+            'c' if is_ecmascript || is_pcre_or_java => self.parse_control_escape(start),
+            'h' | 'H' if is_pcre_or_java => Ok(Self::class(HORIZONTAL_SPACES, character == 'H')),
+            'v' | 'V' if is_pcre_or_java => Ok(Self::class(VERTICAL_SPACES, character == 'V')),
+            'v' => Ok(Item::Character('\u{B}')),
+            'R' if is_pcre_or_java && !in_class => Ok(Item::Raw(format!(
+                "(?:\\x{{D}}\\x{{A}}|[{VERTICAL_SPACES}])"
+            ))),
+            'e' if is_pcre_or_java => Ok(Item::Character('\u{1B}')),
+            'a' if !is_ecmascript => Ok(Item::Character('\u{7}')),
+            'n' => Ok(Item::Character('\n')),
+            'r' => Ok(Item::Character('\r')),
+            't' => Ok(Item::Character('\t')),
+            'f' => Ok(Item::Character('\u{C}')),
+            'b' if in_class => Ok(Item::Character('\u{8}')),
+            'b' | 'B' if has_ascii_classes => Ok(Item::Raw(format!("(?-u:\\{character})"))),
+            'd' | 'D' if has_ascii_classes => Ok(Self::class(ASCII_DIGITS, character == 'D')),
+            'w' | 'W' if has_ascii_classes => {
+                Ok(Self::class(ASCII_WORD_CHARACTERS, character == 'W'))
+            }
+            's' | 'S' if has_ascii_classes => {
+                let spaces = match self.dialect {
+                    Dialect::Re2 => RE2_SPACES,
+                    Dialect::Pcre | Dialect::Java => PCRE_SPACES,
+                    _ => ECMASCRIPT_SPACES,
+                };
+                Ok(Self::class(spaces, character == 'S'))
+            }
+            'b' | 'B' | 'd' | 'D' | 'w' | 'W' | 's' | 'S' => {
+                Ok(Item::Raw(format!("\\{character}")))
+            }
+            'p' | 'P' if !matches!(self.dialect, Dialect::EcmaScript | Dialect::Python) => {
+                self.parse_property(character == 'P', start)
+            }
+            'A' if !in_class && !is_ecmascript => Ok(Item::Raw(String::from("\\A"))),
+            'z' if !in_class && !is_ecmascript => Ok(Item::Raw(String::from("\\z"))),
+            'Z' if !in_class && self.dialect == Dialect::Python => {
+                Ok(Item::Raw(String::from("\\z")))
+            }
+            'Z' if !in_class && is_pcre_or_java => {
+                Err(self.unsupported("end of text assertion", start))
+            }
+            'k' | 'g' if !in_class && (is_pcre_or_java || (character == 'k' && is_ecmascript)) => {
+                match self.peek() {
+                    Some('<') => self.skip_past('>'),
+                    Some('{') => self.skip_past('}'),
+                    Some('\'') => {
+                        self.position += 1;
+                        self.skip_past('\'')
+                    }
+                    _ => self.read_digits(10, usize::MAX).is_some(),
+                };
+                Err(self.non_regular("backreference", start))
+            }
+            'G' | 'K' | 'X' | 'C' if is_pcre_or_java || self.dialect == Dialect::Re2 => {
+                Err(self.unsupported("escape sequence", start))
+            }
+            _ if character.is_ascii_alphanumeric() && self.dialect == Dialect::EcmaScript => {
+                Ok(Item::Character(character))
+            }
+            _ if character.is_ascii_alphanumeric() => {
+                Err(self.invalid("Unknown escape sequence", start))
+            }
+            _ => Ok(Item::Character(character)),
+        }
+    }
+
+    fn parse_hexadecimal_escape(&mut self, start: usize) -> Result<Item, EngineError> {
+        if matches!(self.dialect, Dialect::Pcre | Dialect::Java | Dialect::Re2) && self.eat("{") {
+            return match (self.read_digits(16, usize::MAX), self.eat("}")) {
+                (Some(value), true) => self.code_point(value, start),
+                _ => Err(self.invalid("Invalid hexadecimal escape", start)),
+            };
+        }
+        let digits_start = self.position;
+        match self.read_digits(16, 2) {
+            Some(value) if self.position - digits_start == 2 || self.dialect == Dialect::Pcre => {
+                self.code_point(value, start)
+            }
+            None if self.dialect == Dialect::Pcre => Ok(Item::Character('\0')),
+            _ if self.dialect == Dialect::EcmaScript => {
+                self.position = digits_start;
+                Ok(Item::Character('x'))
+            }
+            _ => Err(self.invalid("Invalid hexadecimal escape", start)),
+        }
+    }
+
+    fn parse_unicode_escape(&mut self, start: usize) -> Result<Item, EngineError> {
+        if self.dialect == Dialect::EcmaScriptUnicode && self.eat("{") {
+            return match (self.read_digits(16, usize::MAX), self.eat("}")) {
+                (Some(value), true) => self.code_point(value, start),
+                _ => Err(self.invalid("Invalid Unicode escape", start)),
+            };
+        }
+        let Some(value) = self.read_exact_hexadecimal(4) else {
+            if self.dialect == Dialect::EcmaScript {
+                return Ok(Item::Character('u'));
+            }
+            return Err(self.invalid("Invalid Unicode escape", start));
+        };
+        if (0xD800..0xDC00).contains(&value) && self.dialect != Dialect::Python {
+            let low_start = self.position;
+            if self.eat("\\u") {
+                if let Some(low) = self
+                    .read_exact_hexadecimal(4)
+                    .filter(|low| (0xDC00..0xE000).contains(low))
+                {
+                    return self.code_point(Self::get_code_point(value, low), start);
+                }
+            }
+            self.position = low_start;
+        }
+        self.code_point(value, start)
+    }
+
+    /// Parse a set of high surrogates followed by a set of low surrogates in ECMAScript, each written as a `\uXXXX` escape or as a class of such escapes and ranges, `None` if the pattern does not continue with them.
+    ///
+    /// This is how [`RegularExpression::to_dialect`] writes the characters outside of the Basic Multilingual Plane, like `[\uD800-\uDBFF][\uDC00-\uDFFF]`.
+    fn parse_surrogate_pairs(&mut self) -> Option<String> {
+        if self.dialect != Dialect::EcmaScript {
+            return None;
+        }
+        let start = self.position;
+        let Some((highs, lows)) = self
+            .read_code_units(0xD800..=0xDBFF)
+            .and_then(|highs| Some((highs, self.read_code_units(0xDC00..=0xDFFF)?)))
+        else {
+            self.position = start;
+            return None;
+        };
+
+        let mut class = String::from("[");
+        let mut push_bounds = |first: u32, last: u32| {
+            write!(class, "\\x{{{first:X}}}-\\x{{{last:X}}}").unwrap();
+        };
+        for (first_high, last_high) in highs {
+            for &(first_low, last_low) in &lows {
+                if first_low == 0xDC00 && last_low == 0xDFFF {
+                    push_bounds(
+                        Self::get_code_point(first_high, first_low),
+                        Self::get_code_point(last_high, last_low),
+                    );
+                } else {
+                    for high in first_high..=last_high {
+                        push_bounds(
+                            Self::get_code_point(high, first_low),
+                            Self::get_code_point(high, last_low),
+                        );
+                    }
+                }
+            }
+        }
+        class.push(']');
+        Some(class)
+    }
+
+    /// Read a `\uXXXX` escape or a class of such escapes and ranges, all within `units`, `None` if the pattern does not continue with one.
+    fn read_code_units(&mut self, units: RangeInclusive<u32>) -> Option<Vec<(u32, u32)>> {
+        let read_code_unit = |parser: &mut Self| {
+            if parser.eat("\\u") {
+                parser
+                    .read_exact_hexadecimal(4)
+                    .filter(|unit| units.contains(unit))
+            } else {
+                None
+            }
+        };
+        if !self.eat("[") {
+            let unit = read_code_unit(self)?;
+            return Some(vec![(unit, unit)]);
+        }
+        let mut bounds = vec![];
+        while !self.eat("]") {
+            let first = read_code_unit(self)?;
+            let last = if self.eat("-") {
+                read_code_unit(self).filter(|last| first <= *last)?
+            } else {
+                first
+            };
+            bounds.push((first, last));
+        }
+        (!bounds.is_empty()).then_some(bounds)
+    }
+
+    #[inline]
+    fn get_code_point(high: u32, low: u32) -> u32 {
+        0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+    }
+
+    fn parse_control_escape(&mut self, start: usize) -> Result<Item, EngineError> {
+        let value = match (self.dialect, self.peek()) {
+            (Dialect::EcmaScript | Dialect::EcmaScriptUnicode, Some(letter))
+                if letter.is_ascii_alphabetic() =>
+            {
+                letter as u32 % 32
+            }
+            (Dialect::Pcre, Some(letter)) if (' '..='~').contains(&letter) => {
+                letter.to_ascii_uppercase() as u32 ^ 0x40
+            }
+            (Dialect::Java, Some(letter)) => letter as u32 ^ 0x40,
+            _ => return Err(self.invalid("Invalid control escape", start)),
+        };
+        self.position += 1;
+        self.code_point(value, start)
+    }
+
+    fn parse_property(&mut self, negated: bool, start: usize) -> Result<Item, EngineError> {
+        let name: String = if self.eat("{") {
+            let name_start = self.position;
+            if !self.skip_past('}') {
+                return Err(self.invalid("Unclosed Unicode property", start));
+            }
+            self.pattern[name_start..self.position - 1].iter().collect()
+        } else {
+            match self.next_char() {
+                Some(name) => name.to_string(),
+                None => return Err(self.invalid("Incomplete escape sequence", start)),
+            }
+        };
+        let (negated, name) = match name.strip_prefix('^') {
+            Some(name) if self.dialect == Dialect::Pcre => (!negated, name),
+            _ => (negated, name.as_str()),
+        };
+        let name = match self.dialect {
+            Dialect::Java => {
+                if let Some((_, class)) = JAVA_POSIX_CLASSES.iter().find(|(java, _)| *java == name)
+                {
+                    let negation = if negated { "^" } else { "" };
+                    return Ok(Item::Raw(format!("[[:{negation}{class}:]]")));
+                }
+                if name.starts_with("java") || (name.starts_with("In") && name != "Inherited") {
+                    return Err(self.unsupported("character property", start));
+                }
+                name.strip_prefix("Is").unwrap_or(name)
+            }
+            Dialect::Pcre if name == "L&" => "LC",
+            _ => name,
+        };
+        let escape = if negated { 'P' } else { 'p' };
+        Ok(Item::Raw(format!("\\{escape}{{{name}}}")))
+    }
+
+    #[inline]
+    fn is_quote(&self) -> bool {
+        matches!(self.dialect, Dialect::Pcre | Dialect::Java | Dialect::Re2)
+            && self.peek_at(1) == Some('Q')
+    }
+
+    /// Parse a `\Q...\E` sequence, whose characters are all literal.
+    fn parse_quote(&mut self) -> String {
+        self.position += 2;
+        let mut quote = String::new();
+        while let Some(character) = self.next_char() {
+            if character == '\\' && self.peek() == Some('E') {
+                self.position += 1;
+                break;
+            }
+            Self::push_literal(&mut quote, character);
+        }
+        quote
+    }
+
+    fn code_point(&self, value: u32, start: usize) -> Result<Item, EngineError> {
+        match char::from_u32(value) {
+            Some(character) => Ok(Item::Character(character)),
+            None if (0xD800..0xE000).contains(&value) => {
+                Err(self.unsupported("lone surrogate", start))
+            }
+            None => Err(self.invalid("Invalid code point", start)),
+        }
+    }
+
+    fn class(set: &str, negated: bool) -> Item {
+        let negation = if negated { "^" } else { "" };
+        Item::Raw(format!("[{negation}{set}]"))
+    }
+
+    fn push_item(regex: &mut String, item: Item) {
+        match item {
+            Item::Character(character) => Self::push_literal(regex, character),
+            Item::Raw(raw) => regex.push_str(&raw),
+        }
+    }
+
+    fn push_literal(regex: &mut String, character: char) {
+        if character.is_ascii_punctuation() && !matches!(character, '<' | '>') {
+            regex.push('\\');
+            regex.push(character);
+        } else if character.is_whitespace() || character.is_control() {
+            write!(regex, "\\x{{{:X}}}", character as u32).unwrap();
+        } else {
+            regex.push(character);
+        }
+    }
+
+    /// Read at most `max` digits in the given radix, `None` if there is none.
+    fn read_digits(&mut self, radix: u32, max: usize) -> Option<u32> {
+        let mut value: Option<u32> = None;
+        let mut count = 0;
+        while count < max {
+            let Some(digit) = self.peek().and_then(|c| c.to_digit(radix)) else {
+                break;
+            };
+            self.position += 1;
+            count += 1;
+            value = Some(
+                value
+                    .unwrap_or(0)
+                    .saturating_mul(radix)
+                    .saturating_add(digit),
+            );
+        }
+        value
+    }
+
+    fn read_exact_hexadecimal(&mut self, length: usize) -> Option<u32> {
+        let start = self.position;
+        let value = self.read_digits(16, length);
+        if self.position - start != length {
+            self.position = start;
+            return None;
+        }
+        value
+    }
+
+    /// Skip the characters up to the given one included, return `false` if it is not found.
+    fn skip_past(&mut self, character: char) -> bool {
+        while let Some(next) = self.next_char() {
+            if next == character {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        let length = prefix.chars().count();
+        if self.position + length <= self.pattern.len()
+            && prefix
+                .chars()
+                .zip(&self.pattern[self.position..])
+                .all(|(expected, actual)| expected == *actual)
+        {
+            self.position += length;
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    #[inline]
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.pattern.get(self.position + offset).copied()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let character = self.peek();
+        if character.is_some() {
+            self.position += 1;
+        }
+        character
+    }
+
+    fn fragment(&self, start: usize) -> String {
+        self.pattern[start..self.position.min(self.pattern.len())]
+            .iter()
+            .collect()
+    }
+
+    fn non_regular(&self, construct: &str, start: usize) -> EngineError {
+        EngineError::RegexSyntaxError(format!(
+            "The {construct} `{}` at position {start} does not describe a regular language",
+            self.fragment(start)
+        ))
+    }
+
+    fn unsupported(&self, construct: &str, start: usize) -> EngineError {
+        EngineError::RegexSyntaxError(format!(
+            "The {construct} `{}` at position {start} is not supported",
+            self.fragment(start)
+        ))
+    }
+
+    fn invalid(&self, message: &str, start: usize) -> EngineError {
+        EngineError::RegexSyntaxError(format!(
+            "{message} `{}` at position {start}",
+            self.fragment(start)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::{Dialect, RegularExpression};
+
+    #[test]
+    fn test_new_with_dialect() -> Result<(), String> {
+        assert_new_with_dialect("a\\h+\\H", Dialect::Pcre, "a[\\t \\xA0\\x{1680}\\x{180E}\\x{2000}-\\x{200A}\\x{202F}\\x{205F}\\x{3000}]+[^\\t \\xA0\\x{1680}\\x{180E}\\x{2000}-\\x{200A}\\x{202F}\\x{205F}\\x{3000}]");
+        assert_new_with_dialect("\\h", Dialect::EcmaScript, "h");
+        assert_new_with_dialect("\\cJ\\cj", Dialect::EcmaScript, "\\n\\n");
+        assert_new_with_dialect("\\c?", Dialect::Pcre, "\\x7F");
+        assert_new_with_dialect("\\x{1F600}\\x41\\x4", Dialect::Pcre, "😀A\\x04");
+        assert_new_with_dialect("\\u{1F600}\\u0041", Dialect::EcmaScriptUnicode, "😀A");
+        assert_new_with_dialect("\\uD83D\\uDE00+", Dialect::EcmaScript, "😀+");
+        assert_new_with_dialect(
+            "[\\uD83D-\\uD83E][\\uDE00\\uDE01]|\\uD83D[\\uDC00-\\uDFFF]",
+            Dialect::EcmaScript,
+            "[\\x{1F600}\\x{1F601}\\x{1FA00}\\x{1FA01}\\x{1F400}-\\x{1F7FF}]",
+        );
+        assert_new_with_dialect("\\U0001F600\\101\\0", Dialect::Python, "😀A\\x00");
+        assert_new_with_dialect("\\d\\w\\s", Dialect::Java, "[0-9][0-9A-Za-z_][\\t-\\r ]");
+        assert_new_with_dialect("\\d", Dialect::Python, "\\d");
+        assert_new_with_dialect(
+            "\\p{Lower}\\P{Alpha}\\p{IsLatin}",
+            Dialect::Java,
+            "[a-z][^A-Za-z]\\p{Latin}",
+        );
+        assert_new_with_dialect("\\p{L&}\\p{^N}", Dialect::Pcre, "\\p{LC}\\P{N}");
+        assert_new_with_dialect("\\Q.*\\E+", Dialect::Pcre, "\\.\\*+");
+        assert_new_with_dialect(
+            "\\R",
+            Dialect::Java,
+            "\\r\\n|[\\n\\x0B\\f\\r\\x{85}\\x{2028}\\x{2029}]",
+        );
+
+        assert_new_with_dialect("[[:alpha:]]", Dialect::Pcre, "[a-zA-Z]");
+        assert_new_with_dialect("[[:alpha:]]", Dialect::PosixEre, "[a-zA-Z]");
+        assert_new_with_dialect("[[:alpha:]]", Dialect::EcmaScript, "[\\[:alph]\\]");
+        assert_new_with_dialect("[[:alpha:]]", Dialect::Python, "[\\[:alph]\\]");
+        assert_new_with_dialect("[[:alpha:]]", Dialect::Java, "[:alph]");
+        assert_new_with_dialect("[a-z&&[^aeiou]]", Dialect::Java, "[a-z&&[^aeiou]]");
+        assert_new_with_dialect("[a&&b~~c]", Dialect::Re2, "[abc&~]");
+        assert_new_with_dialect("[]a\\d-]", Dialect::PosixEre, "[\\]a\\\\d-]");
+        assert_new_with_dialect("[]a]", Dialect::Pcre, "[\\]a]");
+        assert_new_with_dialect("[]", Dialect::EcmaScript, "[]");
+        assert_new_with_dialect("[^]", Dialect::EcmaScript, "(?s:.)");
+        assert_new_with_dialect("[\\w-z]", Dialect::EcmaScript, "[0-9A-Za-z_\\-z]");
+
+        assert_new_with_dialect("a{,3}b{2}c{", Dialect::Python, "a{0,3}b{2}c\\{");
+        assert_new_with_dialect("a{,3}", Dialect::EcmaScript, "a\\{,3\\}");
+        assert_new_with_dialect(
+            "(?<year>\\d{4})-(?'month'\\d\\d)",
+            Dialect::Pcre,
+            "[0-9]{4}-[0-9]{2}",
+        );
+        assert_new_with_dialect("a(?#comment)b*?", Dialect::Python, "ab*");
+        assert_new_with_dialect("(?x) a b # comment\n c", Dialect::Pcre, "abc");
+        assert_new_with_dialect("(?i)a", Dialect::Java, "[aA]");
+        assert_new_with_dialect("a|(?!)b", Dialect::Pcre, "a");
+        assert_new_with_dialect("\\Aa\\Z", Dialect::Python, "^a$");
+
+        assert_error(
+            "(a)\\1",
+            Dialect::Pcre,
+            "The backreference `\\1` at position 3 does not describe a regular language",
+        );
+        assert_error(
+            "(?<a>a)\\k<a>",
+            Dialect::EcmaScript,
+            "The backreference `\\k<a>` at position 7 does not describe a regular language",
+        );
+        assert_error(
+            "(?P<a>a)(?P=a)",
+            Dialect::Python,
+            "The backreference `(?P=a)` at position 8 does not describe a regular language",
+        );
+        assert_error(
+            "a(?R)?b",
+            Dialect::Pcre,
+            "The recursion `(?R)` at position 1 does not describe a regular language",
+        );
+        assert_error(
+            "a(?=b)",
+            Dialect::Java,
+            "The lookahead `(?=` at position 1 is not supported",
+        );
+        assert_error(
+            "(?<!b)a",
+            Dialect::EcmaScript,
+            "The lookbehind `(?<!` at position 0 is not supported",
+        );
+        assert_error(
+            "a*+",
+            Dialect::Java,
+            "The possessive quantifier `*+` at position 1 is not supported",
+        );
+        assert_error(
+            "(?>a)",
+            Dialect::Pcre,
+            "The atomic group `(?>` at position 0 is not supported",
+        );
+        assert_error(
+            "\\uD83D",
+            Dialect::Java,
+            "The lone surrogate `\\uD83D` at position 0 is not supported",
+        );
+        assert_error(
+            "\\q",
+            Dialect::Python,
+            "Unknown escape sequence `\\q` at position 0",
+        );
+        assert_error(
+            "[a",
+            Dialect::Pcre,
+            "Unclosed character class `[a` at position 0",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dot_line_terminators() -> Result<(), String> {
+        for (dialect, line_terminators) in [
+            (Dialect::Pcre, vec!["\n"]),
+            (Dialect::Python, vec!["\n"]),
+            (Dialect::Re2, vec!["\n"]),
+            (Dialect::Rust, vec!["\n"]),
+            (
+                Dialect::EcmaScript,
+                vec!["\n", "\r", "\u{2028}", "\u{2029}"],
+            ),
+            (
+                Dialect::EcmaScriptUnicode,
+                vec!["\n", "\r", "\u{2028}", "\u{2029}"],
+            ),
+            (
+                Dialect::Java,
+                vec!["\n", "\r", "\u{85}", "\u{2028}", "\u{2029}"],
+            ),
+        ] {
+            let dot = RegularExpression::new_with_dialect("a.b", dialect).unwrap();
+            let dot_all = RegularExpression::new_with_dialect("a(?s:.)b", dialect).unwrap();
+            let dot_all_flag = RegularExpression::new_with_dialect("(?s)a.b", dialect).unwrap();
+            let dot_all_disabled =
+                RegularExpression::new_with_dialect("(?s)a(?-s:.)b", dialect).unwrap();
+            let dot = dot.to_automaton().unwrap();
+            let dot_all = dot_all.to_automaton().unwrap();
+            let dot_all_flag = dot_all_flag.to_automaton().unwrap();
+            let dot_all_disabled = dot_all_disabled.to_automaton().unwrap();
+
+            assert!(dot.match_string("a\tb"), "{dialect:?}");
+            assert!(dot.match_string("a\u{B}b"), "{dialect:?}");
+            for line_terminator in line_terminators {
+                let string = format!("a{line_terminator}b");
+                assert!(!dot.match_string(&string), "{dialect:?} {string:?}");
+                assert!(
+                    !dot_all_disabled.match_string(&string),
+                    "{dialect:?} {string:?}"
+                );
+                assert!(dot_all.match_string(&string), "{dialect:?} {string:?}");
+                assert!(dot_all_flag.match_string(&string), "{dialect:?} {string:?}");
+            }
+        }
+
+        let dot = RegularExpression::new_with_dialect("a.b", Dialect::PosixEre).unwrap();
+        assert!(dot.to_automaton().unwrap().match_string("a\nb"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_dialect_round_trip() -> Result<(), String> {
+        for regex in [
+            "a.b",
+            "[^a-z]+",
+            "[a\u{1F600}-\u{1F6FF}]",
+            "[\u{10000}-\u{10FFFF}]x",
+            "(ab|c)*d{2,5}",
+            "😀.é\n",
+            "[^\\n]?\\.\\*",
+            "[\u{1F600}\u{1F680}]{2}",
+            "",
+            "[]",
+        ] {
+            let regex = RegularExpression::new(regex).unwrap();
+            for dialect in [
+                Dialect::Rust,
+                Dialect::Pcre,
+                Dialect::EcmaScript,
+                Dialect::EcmaScriptUnicode,
+                Dialect::Java,
+                Dialect::Python,
+                Dialect::PosixEre,
+                Dialect::Re2,
+            ] {
+                let Ok(written) = regex.to_dialect(dialect) else {
+                    continue;
+                };
+                let parsed = RegularExpression::new_with_dialect(&written, dialect).unwrap();
+                assert!(
+                    regex
+                        .to_automaton()
+                        .unwrap()
+                        .is_equivalent_of(&parsed.to_automaton().unwrap())
+                        .unwrap(),
+                    "{regex} {dialect:?} {written}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn assert_new_with_dialect(regex: &str, dialect: Dialect, expected: &str) {
+        let parsed = RegularExpression::new_with_dialect(regex, dialect).unwrap();
+        let expected = RegularExpression::new(expected).unwrap();
+        assert!(
+            parsed
+                .to_automaton()
+                .unwrap()
+                .is_equivalent_of(&expected.to_automaton().unwrap())
+                .unwrap(),
+            "{regex} {parsed} {expected}"
+        );
+    }
+
+    fn assert_error(regex: &str, dialect: Dialect, expected: &str) {
+        let error = RegularExpression::new_with_dialect(regex, dialect).unwrap_err();
+        assert_eq!(format!("{expected}."), error.to_string());
+    }
+}
@@ -0,0 +1,326 @@
+use regex_charclass::char::Char;
+
+use crate::error::EngineError;
+
+use super::*;
+
+const POSIX_CLASSES: [&str; 12] = [
+    "alnum", "alpha", "blank", "cntrl", "digit", "graph", "lower", "print", "punct", "space",
+    "upper", "xdigit",
+];
+
+/// Options of the glob syntax parsed by [`RegularExpression::new_glob`].
+///
+/// The default options follow the shell globs on `/` separated paths, [`GlobOptions::wildcard`] the wildcards of IAM and S3 policies.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GlobOptions {
+    /// The path separator, which `*`, `?` and the character classes do not match while a `**` segment matches any number of segments. If `None`, `*` matches any sequence of characters.
+    pub separator: Option<char>,
+    /// If `true`, `{a,b}` matches any of the comma separated alternatives.
+    pub alternatives: bool,
+    /// If `true`, `[...]` matches a character of the class, and `[!...]` or `[^...]` a character outside of it.
+    pub character_classes: bool,
+    /// If `true`, a backslash makes the following character literal.
+    pub escape: bool,
+}
+
+impl Default for GlobOptions {
+    fn default() -> Self {
+        GlobOptions {
+            separator: Some('/'),
+            alternatives: true,
+            character_classes: true,
+            escape: true,
+        }
+    }
+}
+
+impl GlobOptions {
+    /// Return the options of the wildcards of IAM and S3 policies: `*` matches any sequence of characters, `?` any character, and all the other characters are literal.
+    pub fn wildcard() -> Self {
+        GlobOptions {
+            separator: None,
+            alternatives: false,
+            character_classes: false,
+            escape: false,
+        }
+    }
+}
+
+impl RegularExpression {
+    /// Parse the given glob pattern with the given options.
+    ///
+    /// A `[` or a `{` that is not closed is literal. Returns [`EngineError::RegexSyntaxError`] if a character class contains a reversed range or an unknown POSIX class.
+    pub fn new_glob(pattern: &str, options: GlobOptions) -> Result<Self, EngineError> {
+        GlobParser {
+            options,
+            pattern: pattern.chars().collect(),
+            position: 0,
+        }
+        .parse_sequence(false)
+    }
+}
+
+struct GlobParser {
+    options: GlobOptions,
+    pattern: Vec<char>,
+    position: usize,
+}
+
+impl GlobParser {
+    /// Parse the pattern up to its end, or up to the end of the current alternative if `in_alternatives` is `true`.
+    fn parse_sequence(&mut self, in_alternatives: bool) -> Result<RegularExpression, EngineError> {
+        let mut regex = RegularExpression::new_empty_string();
+        while let Some(character) = self.peek() {
+            let part = match character {
+                ',' | '}' if in_alternatives => break,
+                '*' => self.parse_stars(in_alternatives),
+                '?' => {
+                    self.position += 1;
+                    RegularExpression::Character(self.any_character())
+                }
+                '[' if self.options.character_classes => self.parse_class()?,
+                '{' if self.options.alternatives => self.parse_alternatives()?,
+                '\\' if self.options.escape && self.position + 1 < self.pattern.len() => {
+                    self.position += 2;
+                    Self::literal(self.pattern[self.position - 1])
+                }
+                _ => {
+                    self.position += 1;
+                    Self::literal(character)
+                }
+            };
+            regex = regex.concat(&part, true);
+        }
+        Ok(regex)
+    }
+
+    fn parse_stars(&mut self, in_alternatives: bool) -> RegularExpression {
+        let start = self.position;
+        while self.peek() == Some('*') {
+            self.position += 1;
+        }
+        let any_sequence = RegularExpression::Character(self.any_character()).repeat(0, None);
+        let Some(separator) = self.options.separator else {
+            return any_sequence;
+        };
+
+        let is_boundary = |character: Option<char>, delimiters: [char; 2]| match character {
+            None => true,
+            Some(character) => {
+                character == separator || (in_alternatives && delimiters.contains(&character))
+            }
+        };
+        let previous = start.checked_sub(1).map(|index| self.pattern[index]);
+        if self.position - start < 2
+            || !is_boundary(previous, ['{', ','])
+            || !is_boundary(self.peek(), [',', '}'])
+        {
+            return any_sequence;
+        }
+
+        // A `**` segment matches any number of segments.
+        let any_path = RegularExpression::new_total();
+        if self.peek() == Some(separator) {
+            self.position += 1;
+            any_path
+                .concat(&Self::literal(separator), true)
+                .repeat(0, Some(1))
+        } else {
+            any_path
+        }
+    }
+
+    fn parse_alternatives(&mut self) -> Result<RegularExpression, EngineError> {
+        let start = self.position;
+        self.position += 1;
+        let mut alternatives = RegularExpression::new_empty();
+        loop {
+            let alternative = self.parse_sequence(true)?;
+            alternatives = alternatives.union(&alternative);
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(alternatives);
+                }
+                _ => {
+                    self.position = start + 1;
+                    return Ok(Self::literal('{'));
+                }
+            }
+        }
+    }
+
+    fn parse_class(&mut self) -> Result<RegularExpression, EngineError> {
+        let start = self.position;
+        self.position += 1;
+        let negated = matches!(self.peek(), Some('!' | '^'));
+        if negated {
+            self.position += 1;
+        }
+
+        let mut range = Range::empty();
+        let mut first = true;
+        loop {
+            let item_start = self.position;
+            let Some(character) = self.next_char() else {
+                self.position = start + 1;
+                return Ok(Self::literal('['));
+            };
+            if character == ']' && !first {
+                break;
+            }
+            first = false;
+
+            if character == '[' && self.peek() == Some(':') {
+                if let Some(class) = self.parse_posix_class(item_start)? {
+                    range = range.union(&class);
+                    continue;
+                }
+            }
+            let first_character = self.class_character(character);
+            let last_character =
+                if self.peek() == Some('-') && self.peek_at(1).is_some_and(|c| c != ']') {
+                    self.position += 1;
+                    let character = self.next_char().unwrap();
+                    self.class_character(character)
+                } else {
+                    first_character
+                };
+            if first_character > last_character {
+                return Err(self.invalid("Invalid character class range", item_start));
+            }
+            range = range.union(&Range::new_from_range(
+                Char::new(first_character)..=Char::new(last_character),
+            ));
+        }
+
+        if negated {
+            range = range.complement();
+        }
+        if let Some(separator) = self.options.separator {
+            range = range.difference(&Self::char_range(separator));
+        }
+        Ok(RegularExpression::Character(range))
+    }
+
+    /// Parse a POSIX class like `[:alpha:]`, `None` if the brackets are not closed.
+    fn parse_posix_class(&mut self, start: usize) -> Result<Option<Range>, EngineError> {
+        let name_start = self.position + 1;
+        let Some(length) = self.pattern[name_start..]
+            .windows(2)
+            .position(|window| window == [':', ']'])
+        else {
+            return Ok(None);
+        };
+        let name: String = self.pattern[name_start..name_start + length]
+            .iter()
+            .collect();
+        self.position = name_start + length + 2;
+        if !POSIX_CLASSES.contains(&name.as_str()) {
+            return Err(self.invalid("Unknown POSIX class", start));
+        }
+        match RegularExpression::new(&format!("[[:{name}:]]")) {
+            Ok(RegularExpression::Character(range)) => Ok(Some(range)),
+            _ => Err(self.invalid("Unknown POSIX class", start)),
+        }
+    }
+
+    /// Return the character of a class starting with `character`, reading the escaped character if needed.
+    fn class_character(&mut self, character: char) -> char {
+        if character == '\\' && self.options.escape {
+            if let Some(escaped) = self.next_char() {
+                return escaped;
+            }
+        }
+        character
+    }
+
+    fn any_character(&self) -> Range {
+        match self.options.separator {
+            Some(separator) => Self::char_range(separator).complement(),
+            None => Range::total(),
+        }
+    }
+
+    #[inline]
+    fn literal(character: char) -> RegularExpression {
+        RegularExpression::Character(Self::char_range(character))
+    }
+
+    #[inline]
+    fn char_range(character: char) -> Range {
+        Range::new_from_range(Char::new(character)..=Char::new(character))
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    #[inline]
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.pattern.get(self.position + offset).copied()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let character = self.peek();
+        if character.is_some() {
+            self.position += 1;
+        }
+        character
+    }
+
+    fn invalid(&self, message: &str, start: usize) -> EngineError {
+        let fragment: String = self.pattern[start..self.position].iter().collect();
+        EngineError::RegexSyntaxError(format!("{message} `{fragment}` at position {start}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::{GlobOptions, RegularExpression};
+
+    #[test]
+    fn test_new_glob() -> Result<(), String> {
+        let path = GlobOptions::default();
+        assert_glob("*.rs", path, "[^/]*\\.rs");
+        assert_glob("src/?.rs", path, "src/[^/]\\.rs");
+        assert_glob("**/*.rs", path, "(.*/)?[^/]*\\.rs");
+        assert_glob("src/**", path, "src/.*");
+        assert_glob("a/**/b", path, "a/(.*/)?b");
+        assert_glob("a**b", path, "a[^/]*b");
+        assert_glob(
+            "{src,tests}/**/*.{rs,toml}",
+            path,
+            "(src|tests)/(.*/)?[^/]*\\.(rs|toml)",
+        );
+        assert_glob("{**/a,b}", path, "(.*/)?a|b");
+        assert_glob("{a,}{b", path, "(a|)\\{b");
+        assert_glob("[a-c!]x[!a]", path, "[a-c!]x[^a/]");
+        assert_glob("[]a][[:digit:]]", path, "[\\]a][0-9]");
+        assert_glob("[\\]]\\*", path, "\\]\\*");
+        assert_glob("[a", path, "\\[a");
+
+        let wildcard = GlobOptions::wildcard();
+        assert_glob("arn:aws:s3:::bucket/*", wildcard, "arn:aws:s3:::bucket/.*");
+        assert_glob("a?[b]{c}\\", wildcard, "a.\\[b\\]\\{c\\}\\\\");
+
+        assert!(RegularExpression::new_glob("[z-a]", path).is_err());
+        assert!(RegularExpression::new_glob("[[:unknown:]]", path).is_err());
+        Ok(())
+    }
+
+    fn assert_glob(pattern: &str, options: GlobOptions, expected: &str) {
+        let glob = RegularExpression::new_glob(pattern, options).unwrap();
+        let expected = RegularExpression::new(expected).unwrap();
+        assert!(
+            glob.to_automaton()
+                .unwrap()
+                .is_equivalent_of(&expected.to_automaton().unwrap())
+                .unwrap(),
+            "{pattern} {glob} {expected}"
+        );
+    }
+}
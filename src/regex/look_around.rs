@@ -0,0 +1,487 @@
+use lazy_static::lazy_static;
+use regex_syntax::hir::{Look, LookSet};
+
+use crate::{error::EngineError, fast_automaton::spanning_set::SpanningSet};
+
+use super::*;
+
+lazy_static! {
+    static ref ASCII_WORD_RANGE: Range = parse_character_class("[0-9A-Za-z_]");
+    static ref UNICODE_WORD_RANGE: Range = parse_character_class("\\w");
+}
+
+fn parse_character_class(class: &str) -> Range {
+    match RegularExpression::new(class) {
+        Ok(RegularExpression::Character(range)) => range,
+        _ => panic!("Can not compile character class {class}."),
+    }
+}
+
+/// The possible contexts surrounding a position: the start or end of the text (index `0`) or a character class (index `i`) that no look-around of the expression can split.
+struct LookContexts {
+    classes: Vec<Range>,
+    representatives: Vec<char>,
+}
+
+impl LookContexts {
+    fn new(look_set: LookSet) -> Self {
+        let mut ranges = vec![];
+        if look_set.contains_anchor_lf() || look_set.contains_anchor_crlf() {
+            ranges.push(Range::new_from_range(Char::new('\n')..=Char::new('\n')));
+        }
+        if look_set.contains_anchor_crlf() {
+            ranges.push(Range::new_from_range(Char::new('\r')..=Char::new('\r')));
+        }
+        if look_set.contains_word_ascii() {
+            ranges.push(ASCII_WORD_RANGE.clone());
+        }
+        if look_set.contains_word_unicode() {
+            ranges.push(UNICODE_WORD_RANGE.clone());
+        }
+
+        let classes = SpanningSet::compute_spanning_set(&ranges).get_spanning_ranges_with_rest();
+        let representatives = classes
+            .iter()
+            .map(|class| class.iter().next().expect("Empty context class.").to_char())
+            .collect();
+        LookContexts {
+            classes,
+            representatives,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.classes.len() + 1
+    }
+
+    fn class(&self, context: usize) -> &Range {
+        &self.classes[context - 1]
+    }
+
+    fn character(&self, context: usize) -> Option<char> {
+        if context == 0 {
+            None
+        } else {
+            Some(self.representatives[context - 1])
+        }
+    }
+
+    /// Return the union of the classes of the contexts satisfying the predicate.
+    fn classes_where(&self, predicate: impl Fn(usize) -> bool) -> Range {
+        (1..self.len())
+            .filter(|context| predicate(*context))
+            .fold(Range::empty(), |range, context| {
+                range.union(self.class(context))
+            })
+    }
+
+    /// Return `true` if the look-around holds between the given previous and next characters, `None` standing for the start or the end of the text.
+    fn look_holds(look: Look, previous: Option<char>, next: Option<char>) -> bool {
+        let is_word_ascii =
+            |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+        let is_word_unicode =
+            |c: Option<char>| c.is_some_and(|c| UNICODE_WORD_RANGE.contains(Char::new(c)));
+
+        match look {
+            Look::Start => previous.is_none(),
+            Look::End => next.is_none(),
+            Look::StartLF => previous.is_none() || previous == Some('\n'),
+            Look::EndLF => next.is_none() || next == Some('\n'),
+            Look::StartCRLF => {
+                previous.is_none()
+                    || previous == Some('\n')
+                    || (previous == Some('\r') && next != Some('\n'))
+            }
+            Look::EndCRLF => {
+                next.is_none()
+                    || next == Some('\r')
+                    || (next == Some('\n') && previous != Some('\r'))
+            }
+            Look::WordAscii => is_word_ascii(previous) != is_word_ascii(next),
+            Look::WordAsciiNegate => is_word_ascii(previous) == is_word_ascii(next),
+            Look::WordUnicode => is_word_unicode(previous) != is_word_unicode(next),
+            Look::WordUnicodeNegate => is_word_unicode(previous) == is_word_unicode(next),
+            Look::WordStartAscii => !is_word_ascii(previous) && is_word_ascii(next),
+            Look::WordEndAscii => is_word_ascii(previous) && !is_word_ascii(next),
+            Look::WordStartUnicode => !is_word_unicode(previous) && is_word_unicode(next),
+            Look::WordEndUnicode => is_word_unicode(previous) && !is_word_unicode(next),
+            Look::WordStartHalfAscii => !is_word_ascii(previous),
+            Look::WordEndHalfAscii => !is_word_ascii(next),
+            Look::WordStartHalfUnicode => !is_word_unicode(previous),
+            Look::WordEndHalfUnicode => !is_word_unicode(next),
+        }
+    }
+}
+
+/// The language of a sub-expression depending on the contexts preceding and following it.
+#[derive(Debug)]
+enum ContextualRegex {
+    /// The language does not depend on the contexts.
+    Uniform(RegularExpression),
+    /// The language for each pair of previous and next contexts.
+    Contextual(Vec<Vec<RegularExpression>>),
+}
+
+impl ContextualRegex {
+    fn from_fn(
+        contexts: &LookContexts,
+        mut f: impl FnMut(usize, usize) -> Result<RegularExpression, EngineError>,
+    ) -> Result<Self, EngineError> {
+        let mut languages = Vec::with_capacity(contexts.len());
+        for previous in 0..contexts.len() {
+            let mut row = Vec::with_capacity(contexts.len());
+            for next in 0..contexts.len() {
+                row.push(f(previous, next)?);
+            }
+            languages.push(row);
+        }
+
+        if languages
+            .iter()
+            .flatten()
+            .all(|regex| regex == &languages[0][0])
+        {
+            Ok(ContextualRegex::Uniform(languages[0][0].clone()))
+        } else {
+            Ok(ContextualRegex::Contextual(languages))
+        }
+    }
+
+    fn get(&self, previous: usize, next: usize) -> &RegularExpression {
+        match self {
+            ContextualRegex::Uniform(regex) => regex,
+            ContextualRegex::Contextual(languages) => &languages[previous][next],
+        }
+    }
+
+    fn union(&self, other: &Self, contexts: &LookContexts) -> Result<Self, EngineError> {
+        if let (ContextualRegex::Uniform(this), ContextualRegex::Uniform(that)) = (self, other) {
+            return Ok(ContextualRegex::Uniform(this.union(that)));
+        }
+        Self::from_fn(contexts, |previous, next| {
+            Ok(self.get(previous, next).union(other.get(previous, next)))
+        })
+    }
+
+    fn concat(&self, other: &Self, contexts: &LookContexts) -> Result<Self, EngineError> {
+        if let (ContextualRegex::Uniform(this), ContextualRegex::Uniform(that)) = (self, other) {
+            return Ok(ContextualRegex::Uniform(this.concat(that, true)));
+        }
+        Self::from_fn(contexts, |previous, next| {
+            Ok(self.concat_in_context(other, previous, next, contexts))
+        })
+    }
+
+    /// Concatenate the two languages in the given contexts, the last character of `self` being the previous context of `other` and the first character of `other` the next context of `self`.
+    fn concat_in_context(
+        &self,
+        other: &Self,
+        previous: usize,
+        next: usize,
+        contexts: &LookContexts,
+    ) -> RegularExpression {
+        // Both parts are non-empty, the parts are grouped by language to keep the expression small.
+        let mut parts: Vec<(RegularExpression, &RegularExpression, Range)> = vec![];
+        for middle_next in 1..contexts.len() {
+            let head = self.get(previous, middle_next);
+            if head.is_empty() {
+                continue;
+            }
+            let mut tails: Vec<(&RegularExpression, Range)> = vec![];
+            for middle_previous in 1..contexts.len() {
+                let tail = other.get(middle_previous, next);
+                if tail.is_empty() {
+                    continue;
+                }
+                let class = contexts.class(middle_previous);
+                match tails.iter_mut().find(|(t, _)| *t == tail) {
+                    Some((_, range)) => *range = range.union(class),
+                    None => tails.push((tail, class.clone())),
+                }
+            }
+
+            let class = contexts.class(middle_next);
+            for (tail, last_range) in tails {
+                let head = head.ending_with(&last_range);
+                if head.is_empty() {
+                    continue;
+                }
+                match parts.iter_mut().find(|(h, t, _)| *h == head && *t == tail) {
+                    Some((_, _, range)) => *range = range.union(class),
+                    None => parts.push((head, tail, class.clone())),
+                }
+            }
+        }
+
+        let mut regex = RegularExpression::new_empty();
+        for (head, tail, first_range) in parts {
+            regex = regex.union(&head.concat(&tail.starting_with(&first_range), true));
+        }
+
+        // Only `self` is non-empty.
+        let last_range =
+            contexts.classes_where(|context| other.get(context, next).accepts_empty_string());
+        if !last_range.is_empty() {
+            regex = regex.union(&self.get(previous, next).ending_with(&last_range));
+        }
+
+        // Only `other` is non-empty.
+        let first_range =
+            contexts.classes_where(|context| self.get(previous, context).accepts_empty_string());
+        if !first_range.is_empty() {
+            regex = regex.union(&other.get(previous, next).starting_with(&first_range));
+        }
+
+        if self.get(previous, next).accepts_empty_string()
+            && other.get(previous, next).accepts_empty_string()
+        {
+            regex = regex.union(&RegularExpression::new_empty_string());
+        }
+        regex
+    }
+
+    fn repeat(
+        &self,
+        min: u32,
+        max_opt: Option<u32>,
+        contexts: &LookContexts,
+    ) -> Result<Self, EngineError> {
+        if let ContextualRegex::Uniform(regex) = self {
+            return Ok(ContextualRegex::Uniform(regex.repeat(min, max_opt)));
+        }
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+
+        let mut regex = ContextualRegex::Uniform(RegularExpression::new_empty_string());
+        for _ in 0..min {
+            execution_profile.assert_not_timed_out()?;
+            regex = regex.concat(self, contexts)?;
+        }
+        if let Some(max) = max_opt {
+            let optional = self.union(
+                &ContextualRegex::Uniform(RegularExpression::new_empty_string()),
+                contexts,
+            )?;
+            for _ in min..max {
+                execution_profile.assert_not_timed_out()?;
+                regex = regex.concat(&optional, contexts)?;
+            }
+            Ok(regex)
+        } else {
+            regex.concat(&self.star(contexts)?, contexts)
+        }
+    }
+
+    /// Compute the Kleene star.
+    ///
+    /// The sequences of non-empty iterations are described by a graph whose inner nodes hold the class of the last character of an iteration
+    /// and the class of the first character of the following one, the graph is then reduced by state elimination.
+    fn star(&self, contexts: &LookContexts) -> Result<Self, EngineError> {
+        let execution_profile = ThreadLocalParams::get_execution_profile();
+        let number_of_classes = contexts.len() - 1;
+        let node = |last: usize, first: usize| 1 + (last - 1) * number_of_classes + (first - 1);
+        let end = number_of_classes * number_of_classes + 1;
+
+        Self::from_fn(contexts, |previous, next| {
+            let mut edges = vec![vec![RegularExpression::new_empty(); end + 1]; end + 1];
+            for last in 1..contexts.len() {
+                for first in 1..contexts.len() {
+                    edges[0][node(last, first)] =
+                        self.get(previous, first).ending_with(contexts.class(last));
+                }
+            }
+            edges[0][end] = self.get(previous, next).without_empty_string();
+
+            for from_last in 1..contexts.len() {
+                for from_first in 1..contexts.len() {
+                    execution_profile.assert_not_timed_out()?;
+                    let from = node(from_last, from_first);
+                    let first_range = contexts.class(from_first);
+                    for last in 1..contexts.len() {
+                        for first in 1..contexts.len() {
+                            edges[from][node(last, first)] = self
+                                .get(from_last, first)
+                                .starting_with(first_range)
+                                .ending_with(contexts.class(last));
+                        }
+                    }
+                    edges[from][end] = self.get(from_last, next).starting_with(first_range);
+                }
+            }
+
+            for state in 1..end {
+                execution_profile.assert_not_timed_out()?;
+                let self_loop = if edges[state][state].is_empty() {
+                    RegularExpression::new_empty_string()
+                } else {
+                    edges[state][state].repeat(0, None)
+                };
+                let outgoing_edges = edges[state].clone();
+                for from in std::iter::once(0).chain(state + 1..end) {
+                    if edges[from][state].is_empty() {
+                        continue;
+                    }
+                    let head = edges[from][state].concat(&self_loop, true);
+                    for (to, edge) in outgoing_edges.iter().enumerate().skip(state + 1) {
+                        if edge.is_empty() {
+                            continue;
+                        }
+                        let path = head.concat(edge, true);
+                        edges[from][to] = edges[from][to].union(&path);
+                    }
+                }
+            }
+
+            Ok(edges[0][end].union(&RegularExpression::new_empty_string()))
+        })
+    }
+}
+
+impl RegularExpression {
+    /// Convert a [`Hir`] containing look-arounds, the anchors and word boundaries are resolved against the characters surrounding them.
+    pub(super) fn convert_look_around_to_regex(hir: &Hir) -> Result<Self, EngineError> {
+        let contexts = LookContexts::new(hir.properties().look_set());
+        Ok(Self::convert_to_contextual_regex(hir, &contexts)?
+            .get(0, 0)
+            .clone())
+    }
+
+    fn convert_to_contextual_regex(
+        hir: &Hir,
+        contexts: &LookContexts,
+    ) -> Result<ContextualRegex, EngineError> {
+        if hir.properties().look_set().is_empty() {
+            return Ok(ContextualRegex::Uniform(Self::convert_to_regex(hir)?));
+        }
+        match hir.kind() {
+            HirKind::Look(look) => ContextualRegex::from_fn(contexts, |previous, next| {
+                if LookContexts::look_holds(
+                    *look,
+                    contexts.character(previous),
+                    contexts.character(next),
+                ) {
+                    Ok(RegularExpression::new_empty_string())
+                } else {
+                    Ok(RegularExpression::new_empty())
+                }
+            }),
+            HirKind::Repetition(repetition) => Self::convert_to_contextual_regex(
+                &repetition.sub,
+                contexts,
+            )?
+            .repeat(repetition.min, repetition.max, contexts),
+            HirKind::Capture(capture) => Self::convert_to_contextual_regex(&capture.sub, contexts),
+            HirKind::Concat(concat) => {
+                let mut concat_regex =
+                    ContextualRegex::Uniform(RegularExpression::new_empty_string());
+                for c in concat {
+                    let concat_value = Self::convert_to_contextual_regex(c, contexts)?;
+                    concat_regex = concat_regex.concat(&concat_value, contexts)?;
+                }
+                Ok(concat_regex)
+            }
+            HirKind::Alternation(alternation) => {
+                let mut alternation_regex =
+                    ContextualRegex::Uniform(RegularExpression::new_empty());
+                for a in alternation {
+                    let alternation_value = Self::convert_to_contextual_regex(a, contexts)?;
+                    alternation_regex = alternation_regex.union(&alternation_value, contexts)?;
+                }
+                Ok(alternation_regex)
+            }
+            _ => Ok(ContextualRegex::Uniform(Self::convert_to_regex(hir)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::regex::Regex;
+
+    use crate::regex::RegularExpression;
+
+    #[test]
+    fn test_anchors() -> Result<(), String> {
+        assert_equivalent("^abc$", "abc");
+        assert_equivalent("\\Aabc\\z", "abc");
+        assert_equivalent("^^a$$", "a");
+        assert_equivalent("a^b", "[]");
+        assert_equivalent("a$b", "[]");
+        assert_equivalent("x(^a|b)c", "xbc");
+        assert_equivalent("(a$|b)c", "bc");
+        assert_equivalent("(^a|b)*", "(a?b*)");
+        assert_equivalent("(a|b$)*", "(a*b?)");
+        assert_equivalent("^\\d$", "\\d");
+        Ok(())
+    }
+
+    #[test]
+    fn test_word_boundaries() -> Result<(), String> {
+        assert_equivalent("\\bfoo\\b", "foo");
+        assert_equivalent("a\\bb", "[]");
+        assert_equivalent("a\\b ", "a ");
+        assert_equivalent("a\\Bb", "ab");
+        assert_equivalent("a\\B ", "[]");
+        Ok(())
+    }
+
+    #[test]
+    fn test_look_around_against_regex_crate() -> Result<(), String> {
+        let patterns = [
+            "^ab$",
+            "(^a|b)c",
+            "x(^a|b)",
+            "a*$",
+            "($|a)b",
+            "(a|^)*b",
+            "\\bab\\b",
+            "(a\\b| )*",
+            "(\\ba)+",
+            "(a|\\b)*b",
+            "(\\Ba|b)*",
+            "( |\\b{start}a|\\b{end}b)*",
+            "(a\\b{start-half}|\\b{end-half} )*",
+            "(?m:^a$\n)*",
+            "(?m:(^|a)\n$)*",
+            "(?Rm:^a$\r?\n)*",
+            "(?-u:\\b)a(?-u:\\B)a",
+        ];
+        let alphabet = ['a', 'b', ' ', '\n', '\r'];
+        let mut strings = vec![String::new()];
+        let mut last = vec![String::new()];
+        for _ in 0..4 {
+            last = last
+                .iter()
+                .flat_map(|s| alphabet.iter().map(move |c| format!("{s}{c}")))
+                .collect();
+            strings.extend(last.iter().cloned());
+        }
+
+        for pattern in patterns {
+            let expected = Regex::new(&format!("(?s)\\A(?:{pattern})\\z")).unwrap();
+            let automaton = RegularExpression::new(pattern)
+                .unwrap()
+                .to_automaton()
+                .unwrap();
+            for string in &strings {
+                assert_eq!(
+                    expected.is_match(string),
+                    automaton.match_string(string),
+                    "{pattern} on {string:?}"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn assert_equivalent(regex: &str, expected: &str) {
+        let automaton = RegularExpression::new(regex)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        let expected = RegularExpression::new(expected)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(automaton.is_equivalent_of(&expected).unwrap(), "{regex}");
+    }
+}
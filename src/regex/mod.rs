@@ -11,9 +11,18 @@ use super::*;
 
 mod analyze;
 mod builder;
+mod derivative;
+mod dialect;
+mod dialect_parser;
+mod glob;
+mod look_around;
 mod operation;
 #[cfg(feature = "serde")]
 mod serializer;
+mod sql;
+
+pub use dialect::Dialect;
+pub use glob::GlobOptions;
 
 /// Represent a regular expression.
 #[derive(Clone, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
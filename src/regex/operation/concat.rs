@@ -215,12 +215,12 @@ impl RegularExpression {
             {
                 if this_range.contains_all(&that_range) && that_min == &0 && this_max_opt.is_none()
                 {
-                    return Some(this.clone());
+                    Some(this.clone())
                 } else {
-                    return None;
+                    None
                 }
             } else {
-                return None;
+                None
             }
         } else if let RegularExpression::Repetition(this_regex, this_min, this_max_opt) = this {
             if **this_regex == *that {
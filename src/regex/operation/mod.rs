@@ -1,6 +1,8 @@
 use super::*;
 
 mod concat;
+mod restrict;
+mod reverse;
 mod simplify;
 mod union;
 
@@ -41,7 +43,7 @@ impl RegularExpression {
                         RegularExpression::Repetition(Box::new(self.clone()), min, max_opt)
                     } else {
                         let r = ((o_max as f64) - 1f64) / ((o_max as f64) - (o_min as f64));
-                        if r > cmp::max(2, min) as f64 {
+                        if min == 0 || r > cmp::max(2, min) as f64 {
                             return RegularExpression::Repetition(
                                 Box::new(self.clone()),
                                 min,
@@ -55,14 +57,11 @@ impl RegularExpression {
                             new_max,
                         )
                     }
-                } else if o_max_opt.is_none()
-                    || max_opt.is_some() && (max_opt.unwrap() == min || max_opt.unwrap() == 1)
-                    || o_max_opt.is_some() && o_max_opt.unwrap() == 1
-                    || max_opt.is_none() && o_min == 0
-                {
+                } else if min > 0 || o_min <= 1 {
                     RegularExpression::Repetition(regular_expression.clone(), min * o_min, new_max)
                 } else {
-                    RegularExpression::Repetition(Box::new(self.clone()), min, max_opt)
+                    // The repetitions of `self` are included in `self`.
+                    RegularExpression::Repetition(Box::new(self.clone()), 0, Some(1))
                 }
             }
             _ => RegularExpression::Repetition(Box::new(self.clone()), min, max_opt),
@@ -201,6 +200,22 @@ mod tests {
             None,
         );
 
+        assert_repeat_simplify(
+            &RangeSet::new_from_range(Char::new('a')..=Char::new('a')),
+            2,
+            Some(3),
+            0,
+            Some(2),
+        );
+
+        assert_repeat_simplify(
+            &RangeSet::new_from_range(Char::new('a')..=Char::new('a')),
+            2,
+            None,
+            0,
+            Some(3),
+        );
+
         Ok(())
     }
 
@@ -0,0 +1,147 @@
+use super::*;
+
+impl RegularExpression {
+    /// Return `true` if the empty string is matched.
+    pub fn accepts_empty_string(&self) -> bool {
+        match self {
+            RegularExpression::Character(_) => false,
+            RegularExpression::Repetition(regular_expression, min, _) => {
+                *min == 0 || regular_expression.accepts_empty_string()
+            }
+            RegularExpression::Concat(elements) => elements
+                .iter()
+                .all(|element| element.accepts_empty_string()),
+            RegularExpression::Alternation(elements) => elements
+                .iter()
+                .any(|element| element.accepts_empty_string()),
+        }
+    }
+
+    /// Return the regular expression matching the non-empty strings of the current one.
+    pub(crate) fn without_empty_string(&self) -> RegularExpression {
+        self.starting_with(&Range::total())
+    }
+
+    /// Return the regular expression matching the strings of the current one starting with a character in the given range.
+    pub(crate) fn starting_with(&self, range: &Range) -> RegularExpression {
+        match self {
+            RegularExpression::Character(character) => {
+                RegularExpression::Character(character.intersection(range))
+            }
+            RegularExpression::Repetition(regular_expression, min, max_opt) => {
+                if max_opt == &Some(0) {
+                    return RegularExpression::new_empty();
+                }
+                let head = regular_expression.starting_with(range);
+                let max_opt = max_opt.map(|max| max - 1);
+                let tail = if regular_expression.accepts_empty_string() {
+                    regular_expression.repeat(0, max_opt)
+                } else {
+                    regular_expression.repeat(min.saturating_sub(1), max_opt)
+                };
+                head.concat(&tail, true)
+            }
+            RegularExpression::Concat(elements) => {
+                let mut regex = RegularExpression::new_empty();
+                for (i, element) in elements.iter().enumerate() {
+                    let tail = elements
+                        .iter()
+                        .skip(i + 1)
+                        .fold(RegularExpression::new_empty_string(), |tail, e| {
+                            tail.concat(e, true)
+                        });
+                    regex = regex.union(&element.starting_with(range).concat(&tail, true));
+                    if !element.accepts_empty_string() {
+                        break;
+                    }
+                }
+                regex
+            }
+            RegularExpression::Alternation(elements) => elements
+                .iter()
+                .fold(RegularExpression::new_empty(), |regex, element| {
+                    regex.union(&element.starting_with(range))
+                }),
+        }
+    }
+
+    /// Return the regular expression matching the strings of the current one ending with a character in the given range.
+    pub(crate) fn ending_with(&self, range: &Range) -> RegularExpression {
+        match self {
+            RegularExpression::Character(character) => {
+                RegularExpression::Character(character.intersection(range))
+            }
+            RegularExpression::Repetition(regular_expression, min, max_opt) => {
+                if max_opt == &Some(0) {
+                    return RegularExpression::new_empty();
+                }
+                let tail = regular_expression.ending_with(range);
+                let max_opt = max_opt.map(|max| max - 1);
+                let head = if regular_expression.accepts_empty_string() {
+                    regular_expression.repeat(0, max_opt)
+                } else {
+                    regular_expression.repeat(min.saturating_sub(1), max_opt)
+                };
+                head.concat(&tail, true)
+            }
+            RegularExpression::Concat(elements) => {
+                let mut regex = RegularExpression::new_empty();
+                for (i, element) in elements.iter().enumerate().rev() {
+                    let head = elements
+                        .iter()
+                        .take(i)
+                        .fold(RegularExpression::new_empty_string(), |head, e| {
+                            head.concat(e, true)
+                        });
+                    regex = regex.union(&head.concat(&element.ending_with(range), true));
+                    if !element.accepts_empty_string() {
+                        break;
+                    }
+                }
+                regex
+            }
+            RegularExpression::Alternation(elements) => elements
+                .iter()
+                .fold(RegularExpression::new_empty(), |regex, element| {
+                    regex.union(&element.ending_with(range))
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex_charclass::char::Char;
+
+    use crate::regex::RegularExpression;
+    use crate::Range;
+
+    #[test]
+    fn test_starting_ending_with() -> Result<(), String> {
+        let range = Range::new_from_range(Char::new('a')..=Char::new('a'));
+
+        assert_restriction("(ab|ba|c)*", |r| r.starting_with(&range), "ab(ab|ba|c)*");
+        assert_restriction("(ab|ba|c)*", |r| r.ending_with(&range), "(ab|ba|c)*ba");
+        assert_restriction("b?a?c", |r| r.starting_with(&range), "ac");
+        assert_restriction("ca?b?", |r| r.ending_with(&range), "ca");
+        assert_restriction("a{2,4}", |r| r.starting_with(&range), "a{2,4}");
+        assert_restriction("(a?b){2}", |r| r.without_empty_string(), "(a?b){2}");
+        assert_restriction("(a?b?){2}", |r| r.without_empty_string(), "(a|b|ab)a?b?");
+        assert_restriction("x*", |r| r.without_empty_string(), "x+");
+        Ok(())
+    }
+
+    fn assert_restriction(
+        regex: &str,
+        restriction: impl Fn(&RegularExpression) -> RegularExpression,
+        expected: &str,
+    ) {
+        let regex = RegularExpression::new(regex).unwrap();
+        let restricted = restriction(&regex).to_automaton().unwrap();
+        let expected = RegularExpression::new(expected)
+            .unwrap()
+            .to_automaton()
+            .unwrap();
+        assert!(restricted.is_equivalent_of(&expected).unwrap(), "{regex}");
+    }
+}
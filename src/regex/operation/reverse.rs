@@ -0,0 +1,49 @@
+use super::*;
+
+impl RegularExpression {
+    /// Return the regular expression matching the reversed strings of the current one.
+    pub fn reverse(&self) -> RegularExpression {
+        match self {
+            RegularExpression::Character(_) => self.clone(),
+            RegularExpression::Repetition(regular_expression, min, max_opt) => {
+                regular_expression.reverse().repeat(*min, *max_opt)
+            }
+            RegularExpression::Concat(elements) => elements
+                .iter()
+                .rev()
+                .fold(RegularExpression::new_empty_string(), |regex, element| {
+                    regex.concat(&element.reverse(), true)
+                }),
+            RegularExpression::Alternation(elements) => elements
+                .iter()
+                .fold(RegularExpression::new_empty(), |regex, element| {
+                    regex.union(&element.reverse())
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::RegularExpression;
+
+    #[test]
+    fn test_reverse() -> Result<(), String> {
+        assert_reverse("abc", "cba");
+        assert_reverse("(abc|de)*f", "f(cba|ed)*");
+        assert_reverse("a(bc|d(ef)+)?", "((fe)+d|cb)?a");
+        assert_reverse("[a-c]{2,5}x", "x[a-c]{2,5}");
+        assert_reverse("", "");
+        assert_reverse("[]", "[]");
+        Ok(())
+    }
+
+    fn assert_reverse(regex: &str, expected: &str) {
+        let regex = RegularExpression::new(regex).unwrap();
+        let expected = RegularExpression::new(expected).unwrap();
+
+        let reversed = regex.reverse();
+        assert_eq!(expected, reversed);
+        assert_eq!(regex, reversed.reverse());
+    }
+}
@@ -1,32 +1,19 @@
 use super::*;
 
 impl RegularExpression {
+    /// Rebuild the regular expression with the simplifications applied when concatenating, alternating and repeating, the returned regular expression is equivalent.
     pub fn simplify(&self) -> Self {
         match self {
             RegularExpression::Character(_) => self.clone(),
             RegularExpression::Repetition(regex, min, max_opt) => {
-                let regex = regex.simplify();
-                match regex {
-                    RegularExpression::Repetition(
-                        simplified_regex,
-                        simplified_min,
-                        simplified_max_opt,
-                    ) => {
-                        let new_max = if let (Some(max), Some(simplified_max)) =
-                            (max_opt, simplified_max_opt)
-                        {
-                            Some(max * simplified_max)
-                        } else {
-                            None
-                        };
-                        RegularExpression::Repetition(
-                            simplified_regex,
-                            min * simplified_min,
-                            new_max,
-                        )
+                let mut regex = regex.simplify();
+                if *min == 0 && max_opt.is_none() {
+                    regex = regex.simplify_in_star();
+                    if regex.is_empty() {
+                        return RegularExpression::new_empty_string();
                     }
-                    _ => RegularExpression::Repetition(Box::new(regex), *min, *max_opt),
                 }
+                regex.repeat(*min, *max_opt)
             }
             RegularExpression::Concat(elements) => {
                 let elements: VecDeque<_> =
@@ -50,4 +37,371 @@ impl RegularExpression {
             }
         }
     }
+    /// Apply the rewrite laws to the regular expression and to its sub-expressions, the returned regular expression is equivalent:
+    /// - adjacent repetitions of the same expression are merged, `a*a*` and `a{2}a{1,3}` become `a*` and `a{3,5}`;
+    /// - a star absorbs an adjacent star of a subset of its characters, `a*[ab]*` becomes `[ab]*`;
+    /// - an alternative matched by a sibling star is removed, `a*|a{2}` becomes `a*`;
+    /// - under a star, the alternatives are normalized as with [`Self::simplify`], `(a*b?)*` becomes `(a|b)*`.
+    pub fn apply_rewrite_laws(&self) -> Self {
+        match self {
+            RegularExpression::Character(_) => self.clone(),
+            RegularExpression::Repetition(regex, min, max_opt) => {
+                let mut regex = regex.apply_rewrite_laws();
+                if *min == 0 && max_opt.is_none() {
+                    regex = regex.simplify_in_star();
+                    if regex.is_empty() {
+                        return RegularExpression::new_empty_string();
+                    }
+                }
+                regex.repeat(*min, *max_opt)
+            }
+            RegularExpression::Concat(elements) => {
+                let mut merged_elements: Vec<RegularExpression> =
+                    Vec::with_capacity(elements.len());
+                for element in elements {
+                    let element = element.apply_rewrite_laws();
+                    match merged_elements
+                        .last()
+                        .and_then(|last| Self::merge_adjacent_repetitions(last, &element))
+                    {
+                        Some(merged) => *merged_elements.last_mut().unwrap() = merged,
+                        None => merged_elements.push(element),
+                    }
+                }
+
+                let mut regex = RegularExpression::new_empty_string();
+                merged_elements
+                    .iter()
+                    .for_each(|element| regex = regex.concat(element, true));
+                regex
+            }
+            RegularExpression::Alternation(elements) => {
+                let elements: Vec<_> = elements
+                    .iter()
+                    .map(|element| element.apply_rewrite_laws())
+                    .collect();
+
+                // Only the kept alternatives can absorb the others, so that of two equal stars one is kept.
+                let mut kept_elements: Vec<RegularExpression> = Vec::with_capacity(elements.len());
+                for element in elements {
+                    if kept_elements
+                        .iter()
+                        .any(|kept| Self::star_absorbs(kept, &element))
+                    {
+                        continue;
+                    }
+                    kept_elements.retain(|kept| !Self::star_absorbs(&element, kept));
+                    kept_elements.push(element);
+                }
+
+                let mut regex = RegularExpression::new_empty();
+                kept_elements
+                    .iter()
+                    .for_each(|element| regex = regex.union(element));
+                regex
+            }
+        }
+    }
+
+    /// Factor the common prefixes and suffixes of the alternatives found with [`Self::get_common_affixes`], in the regular expression and in its sub-expressions: `cat|dog|dot` becomes `cat|do[gt]`. The returned regular expression is equivalent.
+    pub fn factor_common_affixes(&self) -> Self {
+        match self {
+            RegularExpression::Character(_) => self.clone(),
+            RegularExpression::Repetition(regex, min, max_opt) => {
+                regex.factor_common_affixes().repeat(*min, *max_opt)
+            }
+            RegularExpression::Concat(elements) => {
+                let mut regex = RegularExpression::new_empty_string();
+                elements.iter().for_each(|element| {
+                    regex = regex.concat(&element.factor_common_affixes(), true)
+                });
+                regex
+            }
+            RegularExpression::Alternation(elements) => {
+                let mut elements: Vec<_> = elements
+                    .iter()
+                    .map(|element| element.factor_common_affixes())
+                    .collect();
+
+                // The factorization shortening the most the alternation is applied first, each one merges two alternatives so the loop ends.
+                loop {
+                    let mut best_factorization = None;
+                    let mut best_gain = 0;
+                    for i in 0..elements.len() {
+                        for j in i + 1..elements.len() {
+                            let Some(factored) =
+                                Self::factor_common_affixes_of(&elements[i], &elements[j])
+                            else {
+                                continue;
+                            };
+                            // The alternatives and the separator between them are replaced.
+                            let gain = (elements[i].get_length_of_string()
+                                + elements[j].get_length_of_string()
+                                + 1)
+                            .saturating_sub(factored.get_length_of_string());
+                            if gain > best_gain {
+                                best_gain = gain;
+                                best_factorization = Some((i, j, factored));
+                            }
+                        }
+                    }
+                    let Some((i, j, factored)) = best_factorization else {
+                        break;
+                    };
+                    elements.remove(j);
+                    elements[i] = factored;
+                }
+
+                let mut regex = RegularExpression::new_empty();
+                elements
+                    .iter()
+                    .for_each(|element| regex = regex.union(element));
+                regex
+            }
+        }
+    }
+
+    /// Return `this|that` with their common prefix and suffix factored, `None` if they do not have any.
+    fn factor_common_affixes_of(this: &Self, that: &Self) -> Option<Self> {
+        let (prefix, (this_regex, that_regex), suffix) = this.get_common_affixes(that);
+        if prefix.is_none() && suffix.is_none() {
+            return None;
+        }
+        let mut factored = prefix.unwrap_or_else(Self::new_empty_string);
+        factored = factored.concat(&this_regex.union(&that_regex), true);
+        if let Some(suffix) = suffix {
+            factored = factored.concat(&suffix, true);
+        }
+        Some(factored)
+    }
+
+    #[inline]
+    fn get_length_of_string(&self) -> usize {
+        self.to_string().chars().count()
+    }
+
+    /// Return the repetition matching `this` followed by `that` if they repeat the same expression, or if one of them is a star matching the other one and the empty string.
+    fn merge_adjacent_repetitions(this: &Self, that: &Self) -> Option<Self> {
+        let (this_regex, this_min, this_max_opt) = this.as_repetition();
+        let (that_regex, that_min, that_max_opt) = that.as_repetition();
+        if this_regex == that_regex && (this_regex != this || that_regex != that) {
+            let min = this_min.checked_add(that_min)?;
+            let max_opt = match (this_max_opt, that_max_opt) {
+                (Some(this_max), Some(that_max)) => Some(this_max.checked_add(that_max)?),
+                _ => None,
+            };
+            Some(this_regex.repeat(min, max_opt))
+        } else if that.accepts_empty_string() && Self::star_absorbs(this, that) {
+            Some(this.clone())
+        } else if this.accepts_empty_string() && Self::star_absorbs(that, this) {
+            Some(that.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Return `true` if `star` is a star matching every string matched by `regex`, as seen on their structure.
+    fn star_absorbs(star: &Self, regex: &Self) -> bool {
+        let RegularExpression::Repetition(star_regex, 0, None) = star else {
+            return false;
+        };
+        if regex.is_empty_string() {
+            return true;
+        }
+        let (regex, _, _) = regex.as_repetition();
+        match (star_regex.as_ref(), regex) {
+            (RegularExpression::Character(star_range), RegularExpression::Character(range)) => {
+                star_range.contains_all(range)
+            }
+            (star_regex, regex) => star_regex == regex,
+        }
+    }
+
+    /// Return the repeated expression with its bounds, `(self, 1, Some(1))` if it is not a repetition.
+    fn as_repetition(&self) -> (&Self, u32, Option<u32>) {
+        match self {
+            RegularExpression::Repetition(regex, min, max_opt) => (regex, *min, *max_opt),
+            _ => (self, 1, Some(1)),
+        }
+    }
+
+    /// Return a regular expression `r` such that `r*` matches the same strings as the Kleene closure of the current one.
+    ///
+    /// Under a star, the repetitions of at most one occurrence can be replaced by their content, and a concatenation of elements matching the empty string by their alternation: `(a*|b)*` and `(a*b?)*` become `(a|b)*`.
+    fn simplify_in_star(&self) -> Self {
+        match self {
+            RegularExpression::Repetition(regex, min, _) if *min <= 1 => regex.simplify_in_star(),
+            RegularExpression::Concat(elements) if self.accepts_empty_string() => elements
+                .iter()
+                .fold(RegularExpression::new_empty(), |regex, element| {
+                    regex.union(&element.simplify_in_star())
+                }),
+            RegularExpression::Alternation(elements) => elements
+                .iter()
+                .fold(RegularExpression::new_empty(), |regex, element| {
+                    regex.union(&element.simplify_in_star())
+                }),
+            _ => self.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex_charclass::{char::Char, irange::RangeSet};
+
+    use crate::regex::RegularExpression;
+
+    #[test]
+    fn test_simplify() -> Result<(), String> {
+        let a = character('a');
+        let b = character('b');
+
+        assert_simplify(
+            repetition(&repetition(&a, 3, Some(3)), 2, Some(3)),
+            "(a{3}){2,3}",
+        );
+        assert_simplify(
+            repetition(&repetition(&a, 2, Some(3)), 2, Some(3)),
+            "a{4,9}",
+        );
+        assert_simplify(repetition(&repetition(&a, 2, None), 0, Some(3)), "(a{2,})?");
+        assert_simplify(
+            repetition(
+                &RegularExpression::Alternation(vec![repetition(&a, 0, None), b.clone()]),
+                0,
+                None,
+            ),
+            "[ab]*",
+        );
+        assert_simplify(
+            repetition(
+                &RegularExpression::Concat(
+                    vec![repetition(&a, 0, None), repetition(&b, 0, Some(1))].into(),
+                ),
+                0,
+                None,
+            ),
+            "[ab]*",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_rewrite_laws() -> Result<(), String> {
+        let a = character('a');
+        let b = character('b');
+        let a_or_b = RegularExpression::new("[ab]").unwrap();
+
+        assert_rewritten(
+            RegularExpression::Concat(
+                vec![repetition(&a, 0, None), repetition(&a, 0, None)].into(),
+            ),
+            RegularExpression::apply_rewrite_laws,
+            "a*",
+        );
+        assert_rewritten(
+            RegularExpression::Concat(
+                vec![repetition(&a, 2, Some(2)), repetition(&a, 1, Some(3))].into(),
+            ),
+            RegularExpression::apply_rewrite_laws,
+            "a{3,5}",
+        );
+        assert_rewritten(
+            RegularExpression::Concat(
+                vec![
+                    repetition(&a, 0, None),
+                    repetition(&a_or_b, 0, None),
+                    b.clone(),
+                ]
+                .into(),
+            ),
+            RegularExpression::apply_rewrite_laws,
+            "[ab]*b",
+        );
+        assert_rewritten(
+            RegularExpression::Alternation(vec![
+                repetition(&a, 2, Some(2)),
+                repetition(&a, 0, None),
+            ]),
+            RegularExpression::apply_rewrite_laws,
+            "a*",
+        );
+        assert_rewritten(
+            repetition(
+                &RegularExpression::Concat(
+                    vec![repetition(&a, 0, None), repetition(&b, 0, Some(1))].into(),
+                ),
+                0,
+                None,
+            ),
+            RegularExpression::apply_rewrite_laws,
+            "[ab]*",
+        );
+        assert_rewritten(
+            RegularExpression::new("((a|b*)*|[ab]*)").unwrap(),
+            RegularExpression::apply_rewrite_laws,
+            "[ab]*",
+        );
+        assert_rewritten(
+            RegularExpression::new("((a|b*)*|[ab]*|x)").unwrap(),
+            RegularExpression::apply_rewrite_laws,
+            "(x|[ab]*)",
+        );
+        assert_rewritten(
+            RegularExpression::new("((a*b?)*|(b|a)*)c").unwrap(),
+            RegularExpression::apply_rewrite_laws,
+            "[ab]*c",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_factor_common_affixes() -> Result<(), String> {
+        assert_rewritten(
+            RegularExpression::new("cat|dog|dot").unwrap(),
+            RegularExpression::factor_common_affixes,
+            "(cat|do[gt])",
+        );
+        assert_rewritten(
+            RegularExpression::new("x(abc|xbd|abd)*").unwrap(),
+            RegularExpression::factor_common_affixes,
+            "x(ab[cd]|xbd)*",
+        );
+        Ok(())
+    }
+
+    fn assert_rewritten(
+        regex: RegularExpression,
+        rewrite: fn(&RegularExpression) -> RegularExpression,
+        expected: &str,
+    ) {
+        let rewritten = rewrite(&regex);
+        assert_eq!(expected, rewritten.to_string());
+        assert!(regex
+            .to_automaton()
+            .unwrap()
+            .is_equivalent_of(&rewritten.to_automaton().unwrap())
+            .unwrap());
+    }
+
+    fn character(character: char) -> RegularExpression {
+        RegularExpression::Character(RangeSet::new_from_range(
+            Char::new(character)..=Char::new(character),
+        ))
+    }
+
+    fn repetition(regex: &RegularExpression, min: u32, max_opt: Option<u32>) -> RegularExpression {
+        RegularExpression::Repetition(Box::new(regex.clone()), min, max_opt)
+    }
+
+    fn assert_simplify(regex: RegularExpression, expected: &str) {
+        let simplified = regex.simplify();
+        assert_eq!(expected, simplified.to_string());
+        assert!(regex
+            .to_automaton()
+            .unwrap()
+            .is_equivalent_of(&simplified.to_automaton().unwrap())
+            .unwrap());
+    }
 }
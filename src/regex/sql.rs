@@ -0,0 +1,203 @@
+use regex_charclass::char::Char;
+
+use crate::error::EngineError;
+
+use super::*;
+
+/// The escape character of the `SIMILAR TO` patterns, as in PostgreSQL.
+const SIMILAR_TO_ESCAPE: char = '\\';
+
+impl RegularExpression {
+    /// Parse the given SQL `LIKE` pattern, where `%` matches any sequence of characters and `_` any character.
+    ///
+    /// The character following `escape` is literal. Returns [`EngineError::RegexSyntaxError`] if the pattern ends with the escape character.
+    pub fn new_sql_like(pattern: &str, escape: Option<char>) -> Result<Self, EngineError> {
+        let mut regex = RegularExpression::new_empty_string();
+        let mut characters = pattern.chars().enumerate();
+        while let Some((position, character)) = characters.next() {
+            let part = if Some(character) == escape {
+                match characters.next() {
+                    Some((_, escaped)) => Self::sql_literal(escaped),
+                    None => return Err(Self::sql_incomplete_escape(character, position)),
+                }
+            } else if character == '%' {
+                RegularExpression::new_total()
+            } else if character == '_' {
+                RegularExpression::Character(Range::total())
+            } else {
+                Self::sql_literal(character)
+            };
+            regex = regex.concat(&part, true);
+        }
+        Ok(regex)
+    }
+
+    /// Parse the given SQL `SIMILAR TO` pattern.
+    ///
+    /// Besides `%` and `_`, the pattern can use the alternations, groups, quantifiers and bracket expressions of regular expressions, any other character being literal. As in PostgreSQL, the escape character is `\`.
+    pub fn new_sql_similar_to(pattern: &str) -> Result<Self, EngineError> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let mut regex = String::with_capacity(pattern.len());
+        let mut position = 0;
+        while position < pattern.len() {
+            let character = pattern[position];
+            position += 1;
+            match character {
+                SIMILAR_TO_ESCAPE => match pattern.get(position) {
+                    Some(escaped) => {
+                        position += 1;
+                        Self::push_sql_literal(&mut regex, *escaped);
+                    }
+                    None => return Err(Self::sql_incomplete_escape(character, position - 1)),
+                },
+                '%' => regex.push_str(".*"),
+                '_' => regex.push('.'),
+                '|' | '*' | '+' | '?' | '(' | ')' => regex.push(character),
+                '{' => match pattern[position..].iter().position(|c| *c == '}') {
+                    Some(length)
+                        if length > 0
+                            && pattern[position..position + length]
+                                .iter()
+                                .all(|c| c.is_ascii_digit() || *c == ',') =>
+                    {
+                        regex.push('{');
+                        regex.extend(&pattern[position..=position + length]);
+                        position += length + 1;
+                    }
+                    _ => Self::push_sql_literal(&mut regex, character),
+                },
+                '[' => {
+                    position = Self::push_sql_bracket_expression(&mut regex, &pattern, position)?
+                }
+                _ => Self::push_sql_literal(&mut regex, character),
+            }
+        }
+        Self::new(&regex)
+    }
+
+    /// Write the bracket expression whose content starts at `position`, return the position following it.
+    fn push_sql_bracket_expression(
+        regex: &mut String,
+        pattern: &[char],
+        mut position: usize,
+    ) -> Result<usize, EngineError> {
+        let start = position - 1;
+        regex.push('[');
+        if pattern.get(position) == Some(&'^') {
+            position += 1;
+            regex.push('^');
+        }
+        let content_start = position;
+        while let Some(character) = pattern.get(position) {
+            position += 1;
+            match *character {
+                ']' if position - 1 > content_start => {
+                    regex.push(']');
+                    return Ok(position);
+                }
+                '[' if pattern.get(position) == Some(&':') => {
+                    let Some(length) = pattern[position..]
+                        .windows(2)
+                        .position(|window| window == [':', ']'])
+                    else {
+                        break;
+                    };
+                    regex.push('[');
+                    regex.extend(
+                        pattern[position..position + length + 2]
+                            .iter()
+                            .map(|c| c.to_ascii_lowercase()),
+                    );
+                    position += length + 2;
+                }
+                '-' if position - 1 > content_start && pattern.get(position) != Some(&']') => {
+                    regex.push('-');
+                }
+                SIMILAR_TO_ESCAPE if position < pattern.len() => {
+                    position += 1;
+                    Self::push_sql_literal(regex, pattern[position - 1]);
+                }
+                _ => Self::push_sql_literal(regex, *character),
+            }
+        }
+        let fragment: String = pattern[start..].iter().collect();
+        Err(EngineError::RegexSyntaxError(format!(
+            "Unclosed bracket expression `{fragment}` at position {start}"
+        )))
+    }
+
+    fn push_sql_literal(regex: &mut String, character: char) {
+        regex_syntax::escape_into(&character.to_string(), regex);
+    }
+
+    #[inline]
+    fn sql_literal(character: char) -> RegularExpression {
+        RegularExpression::Character(Range::new_from_range(
+            Char::new(character)..=Char::new(character),
+        ))
+    }
+
+    fn sql_incomplete_escape(escape: char, position: usize) -> EngineError {
+        EngineError::RegexSyntaxError(format!(
+            "Incomplete escape sequence `{escape}` at position {position}"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::regex::RegularExpression;
+
+    #[test]
+    fn test_new_sql_like() -> Result<(), String> {
+        assert_sql_like("abc%", None, "abc.*");
+        assert_sql_like("_b%_", None, ".b.*.");
+        assert_sql_like("100!%!_!!%", Some('!'), "100%_!.*");
+        assert_sql_like("a.b[c]", Some('\\'), "a\\.b\\[c\\]");
+        assert_sql_like("\\a\\%", Some('\\'), "a%");
+
+        assert_eq!(
+            "Incomplete escape sequence `!` at position 2.",
+            RegularExpression::new_sql_like("ab!", Some('!'))
+                .unwrap_err()
+                .to_string()
+        );
+        Ok(())
+    }
+
+    fn assert_sql_like(pattern: &str, escape: Option<char>, expected: &str) {
+        let like = RegularExpression::new_sql_like(pattern, escape).unwrap();
+        assert_equivalent(pattern, &like, expected);
+    }
+
+    #[test]
+    fn test_new_sql_similar_to() -> Result<(), String> {
+        assert_sql_similar_to("%(b|d)%", ".*(b|d).*");
+        assert_sql_similar_to("a.b_", "a\\.b.");
+        assert_sql_similar_to("(ab){2,3}c*d+e?", "(ab){2,3}c*d+e?");
+        assert_sql_similar_to("[a-c^][^0-9][[:ALPHA:]]", "[a-c\\^][^0-9][a-zA-Z]");
+        assert_sql_similar_to("[]a]\\%\\[x{y", "[\\]a]%\\[x\\{y");
+
+        assert!(RegularExpression::new_sql_similar_to("[a").is_err());
+        assert!(RegularExpression::new_sql_similar_to("a\\").is_err());
+        assert!(RegularExpression::new_sql_similar_to("(a").is_err());
+        Ok(())
+    }
+
+    fn assert_sql_similar_to(pattern: &str, expected: &str) {
+        let similar_to = RegularExpression::new_sql_similar_to(pattern).unwrap();
+        assert_equivalent(pattern, &similar_to, expected);
+    }
+
+    fn assert_equivalent(pattern: &str, regex: &RegularExpression, expected: &str) {
+        let expected = RegularExpression::new(expected).unwrap();
+        assert!(
+            regex
+                .to_automaton()
+                .unwrap()
+                .is_equivalent_of(&expected.to_automaton().unwrap())
+                .unwrap(),
+            "{pattern} {regex} {expected}"
+        );
+    }
+}
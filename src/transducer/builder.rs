@@ -0,0 +1,230 @@
+use regex_charclass::char::Char;
+
+use super::*;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+impl Transducer {
+    /// Create a transducer replacing the non-overlapping occurrences of `pattern`, from left to right, by `replacement`, like [`str::replace`].
+    pub fn replace_all(pattern: &str, replacement: &str) -> Self {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let replacement = Self::string_output(replacement.chars());
+
+        let mut transducer = Self::new_empty();
+        let final_state = transducer.new_state();
+        transducer.accept(final_state);
+        if pattern.is_empty() {
+            transducer.add_transition(
+                0,
+                0,
+                Some(Range::total()),
+                [replacement.clone(), vec![Output::Input]].concat(),
+            );
+            transducer.add_transition(0, final_state, None, replacement);
+            return transducer;
+        }
+
+        // The state `j` means that the `j` first characters of the pattern have been read and not written yet.
+        let states: Vec<usize> = (0..pattern.len())
+            .map(|j| if j == 0 { 0 } else { transducer.new_state() })
+            .collect();
+        let mut pattern_characters = pattern.clone();
+        pattern_characters.sort_unstable();
+        pattern_characters.dedup();
+        let other_characters = pattern_characters
+            .iter()
+            .fold(Range::total(), |range, character| {
+                range.difference(&Self::char_range(*character))
+            });
+
+        for (j, state) in states.iter().enumerate() {
+            let read = &pattern[..j];
+            transducer.add_transition(
+                *state,
+                final_state,
+                None,
+                Self::string_output(read.iter().copied()),
+            );
+            transducer.add_transition(
+                *state,
+                0,
+                Some(other_characters.clone()),
+                [
+                    Self::string_output(read.iter().copied()),
+                    vec![Output::Input],
+                ]
+                .concat(),
+            );
+            for character in &pattern_characters {
+                let (to_state, output) = if *character == pattern[j] && j + 1 == pattern.len() {
+                    (0, replacement.clone())
+                } else if *character == pattern[j] {
+                    (states[j + 1], vec![])
+                } else {
+                    let read: Vec<char> = read.iter().chain([character]).copied().collect();
+                    let k = (0..=j)
+                        .rev()
+                        .find(|k| read.ends_with(&pattern[..*k]))
+                        .unwrap_or(0);
+                    (
+                        states[k],
+                        Self::string_output(read[..read.len() - k].iter().copied()),
+                    )
+                };
+                transducer.add_transition(
+                    *state,
+                    to_state,
+                    Some(Self::char_range(*character)),
+                    output,
+                );
+            }
+        }
+        transducer
+    }
+
+    /// Create a transducer replacing each of the given characters by its associated string, the other characters are left unchanged.
+    pub fn replace_chars(replacements: &[(char, &str)]) -> Self {
+        let mut transducer = Self::new_empty();
+        transducer.accept(0);
+        let mut other_characters = Range::total();
+        for (character, replacement) in replacements {
+            let range = Self::char_range(*character);
+            other_characters = other_characters.difference(&range);
+            transducer.add_transition(0, 0, Some(range), Self::string_output(replacement.chars()));
+        }
+        transducer.add_transition(0, 0, Some(other_characters), vec![Output::Input]);
+        transducer
+    }
+
+    /// Create a transducer escaping the characters `&`, `<`, `>`, `"` and `'` for HTML.
+    pub fn html_escape() -> Self {
+        Self::replace_chars(&[
+            ('&', "&amp;"),
+            ('<', "&lt;"),
+            ('>', "&gt;"),
+            ('"', "&quot;"),
+            ('\'', "&#x27;"),
+        ])
+    }
+
+    /// Create a transducer percent-encoding the characters that are not unreserved in URIs (`[A-Za-z0-9\-._~]`), as specified by RFC 3986.
+    ///
+    /// The ASCII characters are encoded exactly. A non-ASCII character is encoded as the `%XX` sequence of its UTF-8 bytes, where the hexadecimal digits of each byte are only constrained by the length of the encoding: the transducer relates a non-ASCII character to every encoding of its length, making images and pre-images over-approximations.
+    pub fn url_encode() -> Self {
+        let mut transducer = Self::new_empty();
+        transducer.accept(0);
+        for character in 0..128u8 {
+            let output = if character.is_ascii_alphanumeric() || b"-._~".contains(&character) {
+                vec![Output::Input]
+            } else {
+                Self::percent_encode(character)
+            };
+            transducer.add_transition(0, 0, Some(Self::char_range(character as char)), output);
+        }
+
+        let continuation_byte = [
+            Self::char_range('%'),
+            Self::hex_digits(0x8, 0xB),
+            Self::hex_digits(0x0, 0xF),
+        ];
+        for (first, last, leading_byte) in [
+            (
+                '\u{80}',
+                '\u{7FF}',
+                [Self::hex_digits(0xC, 0xD), Self::hex_digits(0x0, 0xF)],
+            ),
+            (
+                '\u{800}',
+                '\u{FFFF}',
+                [Self::hex_digits(0xE, 0xE), Self::hex_digits(0x0, 0xF)],
+            ),
+            (
+                '\u{10000}',
+                '\u{10FFFF}',
+                [Self::hex_digits(0xF, 0xF), Self::hex_digits(0x0, 0x4)],
+            ),
+        ] {
+            let mut output = vec![Output::Characters(Self::char_range('%'))];
+            output.extend(leading_byte.into_iter().map(Output::Characters));
+            for _ in 1..first.len_utf8() {
+                output.extend(continuation_byte.iter().cloned().map(Output::Characters));
+            }
+            transducer.add_transition(
+                0,
+                0,
+                Some(Range::new_from_range(Char::new(first)..=Char::new(last))),
+                output,
+            );
+        }
+        transducer
+    }
+
+    /// Create a transducer removing the leading and trailing whitespaces, like [`str::trim`].
+    pub fn trim() -> Self {
+        let whitespaces = [
+            ('\t', '\r'),
+            (' ', ' '),
+            ('\u{85}', '\u{85}'),
+            ('\u{A0}', '\u{A0}'),
+            ('\u{1680}', '\u{1680}'),
+            ('\u{2000}', '\u{200A}'),
+            ('\u{2028}', '\u{2029}'),
+            ('\u{202F}', '\u{202F}'),
+            ('\u{205F}', '\u{205F}'),
+            ('\u{3000}', '\u{3000}'),
+        ]
+        .iter()
+        .fold(Range::empty(), |range, (first, last)| {
+            range.union(&Range::new_from_range(Char::new(*first)..=Char::new(*last)))
+        });
+        let others = whitespaces.complement();
+
+        // The state `inside` is reached after a non-whitespace character, `gap` in whitespaces followed by a non-whitespace character and `trailing` in the trailing whitespaces.
+        let mut transducer = Self::new_empty();
+        let leading = transducer.start_state;
+        let inside = transducer.new_state();
+        let gap = transducer.new_state();
+        let trailing = transducer.new_state();
+        transducer.accept(leading);
+        transducer.accept(inside);
+        transducer.accept(trailing);
+
+        transducer.add_transition(leading, leading, Some(whitespaces.clone()), vec![]);
+        transducer.add_transition(leading, inside, Some(others.clone()), vec![Output::Input]);
+        transducer.add_transition(inside, inside, Some(others.clone()), vec![Output::Input]);
+        transducer.add_transition(inside, gap, Some(whitespaces.clone()), vec![Output::Input]);
+        transducer.add_transition(inside, trailing, Some(whitespaces.clone()), vec![]);
+        transducer.add_transition(gap, gap, Some(whitespaces.clone()), vec![Output::Input]);
+        transducer.add_transition(gap, inside, Some(others), vec![Output::Input]);
+        transducer.add_transition(trailing, trailing, Some(whitespaces), vec![]);
+        transducer
+    }
+
+    fn string_output(string: impl Iterator<Item = char>) -> Vec<Output> {
+        string
+            .map(|character| Output::Characters(Self::char_range(character)))
+            .collect()
+    }
+
+    fn percent_encode(byte: u8) -> Vec<Output> {
+        Self::string_output(
+            [
+                '%',
+                HEX_DIGITS[(byte >> 4) as usize] as char,
+                HEX_DIGITS[(byte & 0xF) as usize] as char,
+            ]
+            .into_iter(),
+        )
+    }
+
+    fn hex_digits(first: u8, last: u8) -> Range {
+        (first..=last).fold(Range::empty(), |range, digit| {
+            range.union(&Self::char_range(HEX_DIGITS[digit as usize] as char))
+        })
+    }
+
+    #[inline]
+    fn char_range(character: char) -> Range {
+        Range::new_from_range(Char::new(character)..=Char::new(character))
+    }
+}
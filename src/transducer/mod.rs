@@ -0,0 +1,103 @@
+use crate::{IntSet, Range};
+
+mod builder;
+
+/// A symbol written by a transition of a [`Transducer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// Write the character read by the transition.
+    Input,
+    /// Write any character of the given range.
+    Characters(Range),
+}
+
+/// A transition of a [`Transducer`], reading at most one character and writing a sequence of symbols.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transition {
+    pub(crate) input: Option<Range>,
+    pub(crate) output: Vec<Output>,
+    pub(crate) to_state: usize,
+}
+
+/// Represent a finite state transducer, modeling a function, or more generally a relation, between strings.
+///
+/// Use [`FastAutomaton::image`](crate::fast_automaton::FastAutomaton::image) and [`FastAutomaton::pre_image`](crate::fast_automaton::FastAutomaton::pre_image) to compute how a language is transformed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transducer {
+    transitions: Vec<Vec<Transition>>,
+    start_state: usize,
+    accept_states: IntSet<usize>,
+}
+
+impl Transducer {
+    /// Create a transducer with a single state, which accepts nothing.
+    pub fn new_empty() -> Self {
+        Transducer {
+            transitions: vec![vec![]],
+            start_state: 0,
+            accept_states: IntSet::default(),
+        }
+    }
+
+    pub fn new_state(&mut self) -> usize {
+        self.transitions.push(vec![]);
+        self.transitions.len() - 1
+    }
+
+    #[inline]
+    pub fn accept(&mut self, state: usize) {
+        self.assert_state_exists(state);
+        self.accept_states.insert(state);
+    }
+
+    /// Add a transition reading a character of `input`, or nothing if `input` is `None`, and writing `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the states does not exist, or if `output` contains [`Output::Input`] while `input` is `None`.
+    pub fn add_transition(
+        &mut self,
+        from_state: usize,
+        to_state: usize,
+        input: Option<Range>,
+        output: Vec<Output>,
+    ) {
+        self.assert_state_exists(from_state);
+        self.assert_state_exists(to_state);
+        if input.is_none() && output.contains(&Output::Input) {
+            panic!("A transition reading nothing can not write its input.");
+        }
+        self.transitions[from_state].push(Transition {
+            input,
+            output,
+            to_state,
+        });
+    }
+
+    #[inline]
+    pub fn get_start_state(&self) -> usize {
+        self.start_state
+    }
+
+    #[inline]
+    pub fn get_number_of_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    #[inline]
+    pub fn is_accepted(&self, state: &usize) -> bool {
+        self.accept_states.contains(state)
+    }
+
+    #[inline]
+    pub(crate) fn transitions_from_state(&self, state: usize) -> &[Transition] {
+        &self.transitions[state]
+    }
+
+    #[inline]
+    fn assert_state_exists(&self, state: usize) {
+        if state >= self.transitions.len() {
+            panic!("The state {} does not exist", state);
+        }
+    }
+}